@@ -0,0 +1,329 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{charge_spending_limit, create_pda_account, pda_transfer};
+
+/// Accounts an `AdminAction` may touch. Only those the action needs have to
+/// be present; a missing one fails with `MissingActionAccount`.
+pub struct AdminActionAccounts<'a, 'info> {
+    pub admin_state: &'a mut Account<'info, AdminState>,
+    pub admin_set: Option<&'a mut Account<'info, AdminSet>>,
+    pub program_config: &'a mut Account<'info, ProgramConfig>,
+    pub guardian_state: Option<&'a mut Account<'info, GuardianState>>,
+    pub treasury_state: Option<&'a mut Account<'info, TreasuryState>>,
+    pub treasury_ata: Option<&'a Account<'info, TokenAccount>>,
+    pub destination: Option<&'a Account<'info, TokenAccount>>,
+    pub user_pda: Option<&'a mut Account<'info, UserPDA>>,
+    pub allowed_destination: Option<&'a Account<'info, AllowedDestination>>,
+    pub token_program: Option<&'a Program<'info, Token>>,
+    pub spending_limit: Option<&'a AccountInfo<'info>>,
+    pub payer: Option<&'a AccountInfo<'info>>,
+    pub system_program: Option<&'a Program<'info, System>>,
+}
+
+/// Apply an approved `AdminAction`, emitting the same event the matching
+/// direct instruction would
+pub fn execute_admin_action(
+    action: &AdminAction,
+    accounts: AdminActionAccounts,
+    executed_by: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    match action.clone() {
+        AdminAction::UpdateAdmin { new_admin } => {
+            let old_admin = accounts.admin_state.set_admin(new_admin)?;
+            emit!(AdminUpdated {
+                old_admin,
+                new_admin,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateAdminSet { members, threshold } => {
+            let admin_set = accounts.admin_set.ok_or(ErrorCode::MissingActionAccount)?;
+            if members.is_empty() {
+                // Back to the single admin key
+                admin_set.members.clear();
+                admin_set.threshold = 0;
+                admin_set.version = admin_set.version.wrapping_add(1);
+                accounts.admin_state.multisig_enabled = false;
+            } else {
+                admin_set.set_members(members, threshold)?;
+                accounts.admin_state.multisig_enabled = true;
+            }
+            emit!(AdminSetUpdated {
+                members: admin_set.members.clone(),
+                threshold: admin_set.threshold,
+                version: admin_set.version,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::TreasuryPayout { mint, destination, amount } => {
+            require!(amount > 0, ErrorCode::InvalidAmount);
+            require!(!accounts.program_config.paused, ErrorCode::ProgramPaused);
+            let treasury_state = accounts.treasury_state.ok_or(ErrorCode::MissingActionAccount)?;
+            let treasury_ata = accounts.treasury_ata.ok_or(ErrorCode::MissingActionAccount)?;
+            let destination_ata = accounts.destination.ok_or(ErrorCode::MissingActionAccount)?;
+            let token_program = accounts.token_program.ok_or(ErrorCode::MissingActionAccount)?;
+
+            require_keys_eq!(treasury_state.token_mint, mint, ErrorCode::InvalidTokenMint);
+            require_keys_eq!(treasury_ata.key(), treasury_state.treasury_ata, ErrorCode::InvalidTreasuryATA);
+            require_keys_eq!(destination_ata.key(), destination, ErrorCode::InvalidPayoutDestination);
+            require_keys_eq!(destination_ata.mint, mint, ErrorCode::InvalidTokenMint);
+            require!(treasury_ata.amount >= amount, ErrorCode::InsufficientBalance);
+
+            // Only allowlisted wallets, once their activation delay has passed
+            let allowed_destination = accounts.allowed_destination.ok_or(ErrorCode::DestinationNotAllowed)?;
+            require!(
+                allowed_destination.mint == mint && allowed_destination.owner == destination_ata.owner,
+                ErrorCode::DestinationNotAllowed
+            );
+            require!(allowed_destination.is_active(now), ErrorCode::DestinationNotActive);
+
+            // Payouts are limited per treasury
+            charge_treasury_limit(accounts.spending_limit, treasury_state.key(), amount, now)?;
+
+            let seeds = &[
+                b"treasury",
+                mint.as_ref(),
+                &[treasury_state.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            pda_transfer(
+                token_program.to_account_info(),
+                treasury_ata.to_account_info(),
+                destination_ata.to_account_info(),
+                treasury_state.to_account_info(),
+                signer_seeds,
+                amount,
+            )?;
+
+            msg!("🏦 Paid {} tokens from the treasury to {}", amount, destination);
+
+            emit!(TreasuryPayoutExecuted {
+                mint,
+                destination,
+                amount,
+                executed_by,
+            });
+        }
+        AdminAction::UpdateDepositLimits { mint, min_deposit, max_deposit } => {
+            let treasury_state = accounts.treasury_state.ok_or(ErrorCode::MissingActionAccount)?;
+            require_keys_eq!(treasury_state.token_mint, mint, ErrorCode::InvalidTokenMint);
+            treasury_state.set_deposit_limits(min_deposit, max_deposit)?;
+            emit!(DepositLimitsUpdated {
+                token_mint: mint,
+                min_deposit,
+                max_deposit,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateTierLimits { tier, max_balance, daily_limit } => {
+            accounts.program_config.set_tier_limits(tier, max_balance, daily_limit)?;
+            accounts.program_config.updated_at = now;
+            emit!(TierLimitsUpdated {
+                tier,
+                max_balance,
+                daily_limit,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateFeeConfig { fee_bps, fee_recipient } => {
+            accounts.program_config.set_fee_config(fee_bps, fee_recipient)?;
+            accounts.program_config.updated_at = now;
+            emit!(FeeConfigUpdated {
+                fee_bps,
+                fee_recipient,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateReferralConfig { referral_bps } => {
+            accounts.program_config.set_referral_bps(referral_bps)?;
+            accounts.program_config.updated_at = now;
+            emit!(ReferralConfigUpdated {
+                referral_bps,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateSweepSplits { splits } => {
+            accounts.program_config.set_sweep_splits(&splits)?;
+            accounts.program_config.updated_at = now;
+            emit!(SweepSplitsUpdated {
+                splits,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateTimelockDelay { delay } => {
+            validate_timelock_delay(delay)?;
+            let old_delay = accounts.admin_state.timelock_delay;
+            accounts.admin_state.timelock_delay = delay;
+            emit!(TimelockDelayUpdated {
+                old_delay,
+                new_delay: delay,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateDestinationDelay { delay } => {
+            let old_delay = accounts.program_config.destination_delay;
+            accounts.program_config.set_destination_delay(delay)?;
+            accounts.program_config.updated_at = now;
+            emit!(DestinationDelayUpdated {
+                old_delay,
+                new_delay: delay,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateKycConfig { attester, kyc_threshold, kyc_min_level } => {
+            accounts.program_config.set_kyc_config(attester, kyc_threshold, kyc_min_level)?;
+            accounts.program_config.updated_at = now;
+            emit!(KycConfigUpdated {
+                attester,
+                kyc_threshold,
+                kyc_min_level,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::SetGuardian { guardian } => {
+            let guardian_state = accounts.guardian_state.ok_or(ErrorCode::MissingActionAccount)?;
+            let old_guardian = guardian_state.set_guardian(guardian, now)?;
+            emit!(GuardianUpdated {
+                old_guardian,
+                new_guardian: guardian,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::UpdateVoucherSigner { voucher_signer } => {
+            let old_signer = accounts.program_config.voucher_signer;
+            accounts.program_config.voucher_signer = voucher_signer;
+            accounts.program_config.updated_at = now;
+            emit!(VoucherSignerUpdated {
+                old_signer,
+                new_signer: voucher_signer,
+                updated_by: executed_by,
+            });
+        }
+        AdminAction::RefundToUser { user_id, mint, amount, reason_code, original_tx_ref } => {
+            require!(amount > 0, ErrorCode::InvalidAmount);
+            require!(!accounts.program_config.paused, ErrorCode::ProgramPaused);
+            let treasury_state = accounts.treasury_state.ok_or(ErrorCode::MissingActionAccount)?;
+            let treasury_ata = accounts.treasury_ata.ok_or(ErrorCode::MissingActionAccount)?;
+            let user_pda = accounts.user_pda.ok_or(ErrorCode::MissingActionAccount)?;
+            let user_pda_ata = accounts.destination.ok_or(ErrorCode::MissingActionAccount)?;
+            let token_program = accounts.token_program.ok_or(ErrorCode::MissingActionAccount)?;
+
+            let (expected_user_pda, _) = Pubkey::find_program_address(
+                &[b"deposit", user_id.as_bytes()],
+                &crate::ID,
+            );
+            require_keys_eq!(user_pda.key(), expected_user_pda, ErrorCode::InvalidPDA);
+            require_keys_eq!(treasury_state.token_mint, mint, ErrorCode::InvalidTokenMint);
+            require_keys_eq!(treasury_ata.key(), treasury_state.treasury_ata, ErrorCode::InvalidTreasuryATA);
+            require_keys_eq!(user_pda_ata.key(), user_pda.token_account, ErrorCode::InvalidUserATA);
+            require_keys_eq!(user_pda_ata.mint, mint, ErrorCode::InvalidTokenMint);
+            require!(treasury_ata.amount >= amount, ErrorCode::InsufficientBalance);
+
+            // Refunds count against the treasury's limit like payouts
+            charge_treasury_limit(accounts.spending_limit, treasury_state.key(), amount, now)?;
+
+            let seeds = &[
+                b"treasury",
+                mint.as_ref(),
+                &[treasury_state.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            pda_transfer(
+                token_program.to_account_info(),
+                treasury_ata.to_account_info(),
+                user_pda_ata.to_account_info(),
+                treasury_state.to_account_info(),
+                signer_seeds,
+                amount,
+            )?;
+
+            user_pda.total_refunded = user_pda
+                .total_refunded
+                .checked_add(amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            treasury_state.total_refunded = treasury_state
+                .total_refunded
+                .checked_add(amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            msg!("↩️ Refunded {} tokens from treasury to user '{}' (reason {})", amount, user_id, reason_code);
+
+            emit!(RefundIssued {
+                user_id,
+                amount,
+                reason_code,
+                original_tx_ref,
+                admin: executed_by,
+                to_ata: user_pda_ata.key(),
+                user_total_refunded: user_pda.total_refunded,
+                treasury_total_refunded: treasury_state.total_refunded,
+            });
+        }
+        AdminAction::SetSpendingLimit { key, max_per_tx, max_per_window } => {
+            let spending_limit = accounts.spending_limit.ok_or(ErrorCode::MissingActionAccount)?;
+            let (expected_limit, bump) = Pubkey::find_program_address(
+                &[b"spending_limit", key.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(spending_limit.key(), expected_limit, ErrorCode::InvalidPDA);
+
+            let mut limit = if spending_limit.data_is_empty() {
+                let payer = accounts.payer.ok_or(ErrorCode::MissingActionAccount)?;
+                let system_program = accounts.system_program.ok_or(ErrorCode::MissingActionAccount)?;
+                create_pda_account(
+                    spending_limit,
+                    SpendingLimit::SPACE,
+                    &[&[b"spending_limit", key.as_ref(), &[bump]]],
+                    payer,
+                    &system_program.to_account_info(),
+                )?;
+                SpendingLimit {
+                    key,
+                    max_per_tx: 0,
+                    max_per_window: 0,
+                    window_start: 0,
+                    window_spent: 0,
+                    bump,
+                    updated_at: 0,
+                }
+            } else {
+                require_keys_eq!(*spending_limit.owner, crate::ID, ErrorCode::InvalidPDA);
+                SpendingLimit::try_deserialize(&mut &spending_limit.try_borrow_data()?[..])?
+            };
+            limit.set_caps(max_per_tx, max_per_window, now)?;
+            limit.try_serialize(&mut &mut spending_limit.try_borrow_mut_data()?[..])?;
+
+            emit!(SpendingLimitUpdated {
+                key,
+                max_per_tx,
+                max_per_window,
+                updated_by: executed_by,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Charge a treasury outflow to the `SpendingLimit` PDA of `treasury`
+fn charge_treasury_limit(
+    spending_limit: Option<&AccountInfo>,
+    treasury: Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let spending_limit = spending_limit.ok_or(ErrorCode::MissingActionAccount)?;
+    let (expected_limit, _) = Pubkey::find_program_address(
+        &[b"spending_limit", treasury.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(spending_limit.key(), expected_limit, ErrorCode::InvalidPDA);
+    charge_spending_limit(spending_limit, amount, now)
+}
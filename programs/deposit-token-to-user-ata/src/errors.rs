@@ -37,4 +37,176 @@ pub enum ErrorCode {
     TooManyAccounts,
     #[msg("Arithmetic overflow occurred")]
     ArithmeticOverflow,
+    #[msg("Depositor is neither owner nor delegate of the source token account")]
+    UnauthorizedDepositSource,
+    #[msg("Deposit exceeds the amount delegated to the depositor")]
+    InsufficientDelegatedAmount,
+    #[msg("Memo too long (max 64 bytes)")]
+    MemoTooLong,
+    #[msg("A reference is required when a deposit receipt is supplied")]
+    MissingReference,
+    #[msg("Deposit reference has already been used")]
+    DuplicateReference,
+    #[msg("A nonce is required when a deposit receipt is supplied")]
+    MissingNonce,
+    #[msg("Duplicate deposit: nonce has already been used")]
+    DuplicateDeposit,
+    #[msg("Deposit receipt is still within its retention window")]
+    ReceiptRetentionActive,
+    #[msg("Only the original depositor can close this receipt")]
+    UnauthorizedReceiptClose,
+    #[msg("Deposit amount is below the minimum for this mint")]
+    DepositBelowMinimum,
+    #[msg("Deposit amount is above the maximum for this mint")]
+    DepositAboveMaximum,
+    #[msg("Minimum deposit cannot exceed maximum deposit")]
+    InvalidDepositLimits,
+    #[msg("Deposit would exceed the balance cap or daily limit for this user's tier")]
+    DepositLimitExceeded,
+    #[msg("Invalid user tier")]
+    InvalidTier,
+    #[msg("Account is not a program account of the expected type")]
+    InvalidMigrationAccount,
+    #[msg("Fee exceeds the maximum allowed (500 bps)")]
+    FeeTooHigh,
+    #[msg("Fee recipient must be set when a fee is charged")]
+    InvalidFeeRecipient,
+    #[msg("Fee vault account is required while a deposit fee is configured")]
+    MissingFeeVault,
+    #[msg("Too many sweep split recipients (maximum 4 allowed)")]
+    TooManySplits,
+    #[msg("Sweep split weights must be non-zero and sum to 10,000 bps")]
+    InvalidSplitConfig,
+    #[msg("Split destination is not the recipient's associated token account")]
+    InvalidSplitDestination,
+    #[msg("Plan period must be greater than 0")]
+    InvalidPlanPeriod,
+    #[msg("Subscription does not belong to this plan")]
+    InvalidPlan,
+    #[msg("Subscription is not due yet")]
+    SubscriptionNotDue,
+    #[msg("Unauthorized: Only the deposit account owner can perform this action")]
+    UnauthorizedUserOwner,
+    #[msg("Subscription is not active")]
+    SubscriptionInactive,
+    #[msg("Subscription is already on this plan")]
+    SamePlan,
+    #[msg("Sale window is invalid")]
+    InvalidSaleWindow,
+    #[msg("Sale is not open for purchases")]
+    SaleNotActive,
+    #[msg("Purchase would exceed the sale hard cap")]
+    SaleHardCapExceeded,
+    #[msg("Sale has not ended yet")]
+    SaleNotEnded,
+    #[msg("Sale has already been finalized")]
+    SaleAlreadyFinalized,
+    #[msg("Refunds are only available for failed sales")]
+    SaleNotFailed,
+    #[msg("Lock schedule is invalid")]
+    InvalidLockSchedule,
+    #[msg("Lock schedule required when passing the lock account")]
+    MissingLockSchedule,
+    #[msg("Lock account is required for this deposit account")]
+    MissingLockAccount,
+    #[msg("Amount exceeds the unlocked balance")]
+    FundsLocked,
+    #[msg("Only the account owner or the admin can lock deposits")]
+    UnauthorizedLock,
+    #[msg("Escrow buyer and seller must be different deposit accounts")]
+    InvalidEscrowParties,
+    #[msg("Escrow has already been settled")]
+    EscrowAlreadySettled,
+    #[msg("Source and destination user must differ")]
+    SameUserTransfer,
+    #[msg("Voucher signature instruction is missing or does not match")]
+    InvalidVoucherSignature,
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    #[msg("Voucher has already been redeemed")]
+    VoucherAlreadyRedeemed,
+    #[msg("No voucher signer is configured")]
+    VoucherSignerNotSet,
+    #[msg("Distribution parameters are invalid")]
+    InvalidDistribution,
+    #[msg("Merkle proof does not match the distribution root")]
+    InvalidMerkleProof,
+    #[msg("Distribution leaf has already been claimed")]
+    AlreadyClaimed,
+    #[msg("Distribution has expired")]
+    DistributionExpired,
+    #[msg("Distribution has not expired yet")]
+    DistributionNotExpired,
+    #[msg("Distribution has already been clawed back")]
+    DistributionAlreadyClawedBack,
+    #[msg("Referrer account does not match")]
+    InvalidReferrer,
+    #[msg("Referral reward exceeds the maximum allowed")]
+    ReferralRewardTooHigh,
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+    #[msg("Admin multisig is enabled; submit this change as a proposal")]
+    MultisigRequired,
+    #[msg("Admin multisig is not enabled")]
+    MultisigNotEnabled,
+    #[msg("Admin set members or threshold are invalid")]
+    InvalidAdminSet,
+    #[msg("Signer is not a member of the admin set")]
+    NotAdminSetMember,
+    #[msg("Admin action is invalid")]
+    InvalidAdminAction,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    #[msg("Member has already approved this proposal")]
+    ProposalAlreadyApproved,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Admin set changed since the proposal was created")]
+    StaleProposal,
+    #[msg("Proposal does not have enough approvals")]
+    ThresholdNotMet,
+    #[msg("An account required by this action is missing")]
+    MissingActionAccount,
+    #[msg("Payout destination does not match the action")]
+    InvalidPayoutDestination,
+    #[msg("Timelock is enabled; queue this change instead")]
+    TimelockRequired,
+    #[msg("Timelock is not enabled")]
+    TimelockNotEnabled,
+    #[msg("Timelock delay is out of range")]
+    InvalidTimelockDelay,
+    #[msg("Queued action is not ready to execute yet")]
+    TimelockNotReady,
+    #[msg("Guardian cannot be the zero address")]
+    InvalidGuardian,
+    #[msg("Signer is not the guardian")]
+    UnauthorizedGuardian,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("User deposit account is frozen")]
+    AccountFrozen,
+    #[msg("Spending limit exceeded")]
+    RateLimitExceeded,
+    #[msg("Per-transaction cap cannot exceed the window cap")]
+    InvalidSpendingLimit,
+    #[msg("No spending limit is configured for this key")]
+    SpendingLimitRequired,
+    #[msg("Raising or removing a spending limit needs a proposal or queued action")]
+    LimitIncreaseNotAllowed,
+    #[msg("Payout destination is not on the allowlist")]
+    DestinationNotAllowed,
+    #[msg("Payout destination is not active yet")]
+    DestinationNotActive,
+    #[msg("Destination activation delay is out of range")]
+    InvalidDestinationDelay,
+    #[msg("Deposits from this wallet are blocked")]
+    DepositorBlocked,
+    #[msg("A valid KYC attestation is required for this amount")]
+    KycRequired,
+    #[msg("Attester cannot be the zero address while KYC is required")]
+    InvalidAttester,
+    #[msg("Signer is not the configured attester")]
+    UnauthorizedAttester,
+    #[msg("Attestation level or expiry is invalid")]
+    InvalidAttestation,
 }
\ No newline at end of file
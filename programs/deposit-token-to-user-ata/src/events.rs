@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{AdminAction, SweepSplit};
 
 #[event]
 pub struct ProgramInitialized {
@@ -20,8 +21,21 @@ pub struct UserDepositAccountCreated {
 pub struct TokensDeposited {
     pub user_id: String,
     pub amount: u64,
+    pub fee_amount: u64,
     pub depositor: Pubkey,
+    pub source: Pubkey,
     pub pda_ata: Pubkey,
+    pub reference: Option<[u8; 32]>,
+    pub memo: Option<String>,
+    pub nonce: Option<u64>,
+}
+
+#[event]
+pub struct DepositReceiptClosed {
+    pub receipt: Pubkey,
+    pub user_pda: Pubkey,
+    pub depositor: Pubkey,
+    pub nonce: u64,
 }
 
 #[event]
@@ -40,4 +54,443 @@ pub struct AdminUpdated {
     pub old_admin: Pubkey,
     pub new_admin: Pubkey,
     pub updated_by: Pubkey,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct DepositLimitsUpdated {
+    pub token_mint: Pubkey,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct TierLimitsUpdated {
+    pub tier: u8,
+    pub max_balance: u64,
+    pub daily_limit: u64,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct UserTierUpdated {
+    pub user_id: String,
+    pub old_tier: u8,
+    pub new_tier: u8,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct FeeConfigUpdated {
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct SweepSplitsUpdated {
+    pub splits: Vec<SweepSplit>,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct SweepSplitApplied {
+    pub user_id: String,
+    pub amount: u64,
+    pub legs: Vec<SweepLeg>,
+}
+
+// One leg of a split sweep
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SweepLeg {
+    pub recipient: Pubkey,
+    pub destination: Pubkey,
+    pub bps: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PlanCreated {
+    pub plan_id: u64,
+    pub plan: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub period: i64,
+    pub grace_period: i64,
+}
+
+#[event]
+pub struct SubscriptionCreated {
+    pub user_id: String,
+    pub subscription: Pubkey,
+    pub plan: Pubkey,
+    pub next_charge_at: i64,
+}
+
+#[event]
+pub struct SubscriptionCharged {
+    pub user_id: String,
+    pub subscription: Pubkey,
+    pub plan: Pubkey,
+    pub amount: u64,
+    pub credit_applied: u64,
+    pub next_charge_at: i64,
+}
+
+#[event]
+pub struct SubscriptionLapsed {
+    pub user_id: String,
+    pub subscription: Pubkey,
+    pub plan: Pubkey,
+    pub amount_due: u64,
+    pub balance: u64,
+    pub grace_ends_at: i64,
+    pub inactive: bool,
+}
+
+#[event]
+pub struct SubscriptionPlanChanged {
+    pub user_id: String,
+    pub subscription: Pubkey,
+    pub old_plan: Pubkey,
+    pub new_plan: Pubkey,
+    pub prorated_credit: u64,
+    pub charged: u64,
+    pub refunded: u64,
+    pub carried_forward: u64,
+    pub next_charge_at: i64,
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub user_id: String,
+    pub subscription: Pubkey,
+    pub plan: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct SaleCreated {
+    pub sale: Pubkey,
+    pub mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub price: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+}
+
+#[event]
+pub struct SaleTokensPurchased {
+    pub user_id: String,
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub cost: u64,
+    pub user_sale_ata: Pubkey,
+}
+
+#[event]
+pub struct SaleFinalized {
+    pub sale: Pubkey,
+    pub succeeded: bool,
+    pub raised: u64,
+    pub sold: u64,
+}
+
+#[event]
+pub struct SaleRefunded {
+    pub user_id: String,
+    pub sale: Pubkey,
+    pub tokens_burned: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct TokensLocked {
+    pub user_id: String,
+    pub amount: u64,
+    pub total_locked: u64,
+    pub unlock_at: i64,
+    pub vesting_start: i64,
+}
+
+#[event]
+pub struct EscrowFunded {
+    pub escrow_id: u64,
+    pub escrow: Pubkey,
+    pub buyer_pda: Pubkey,
+    pub seller_pda: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscrowReleased {
+    pub escrow_id: u64,
+    pub escrow: Pubkey,
+    pub seller_pda: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct EscrowRefunded {
+    pub escrow_id: u64,
+    pub escrow: Pubkey,
+    pub buyer_pda: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct EscrowDisputeResolved {
+    pub escrow_id: u64,
+    pub escrow: Pubkey,
+    pub release_to_seller: bool,
+    pub amount: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct UserToUserTransfer {
+    pub from_user_id: String,
+    pub to_user_id: String,
+    pub amount: u64,
+    pub authority: Pubkey,
+    pub from_ata: Pubkey,
+    pub to_ata: Pubkey,
+}
+
+#[event]
+pub struct RefundIssued {
+    pub user_id: String,
+    pub amount: u64,
+    pub reason_code: u16,
+    pub original_tx_ref: [u8; 64], // Signature of the sweep being reversed
+    pub admin: Pubkey,
+    pub to_ata: Pubkey,
+    pub user_total_refunded: u64,
+    pub treasury_total_refunded: u64,
+}
+
+#[event]
+pub struct VoucherRedeemed {
+    pub user_id: String,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub nonce: u64,
+    pub expiry: i64,
+}
+
+#[event]
+pub struct VoucherSignerUpdated {
+    pub old_signer: Pubkey,
+    pub new_signer: Pubkey,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct DistributionCreated {
+    pub distribution_id: u64,
+    pub distribution: Pubkey,
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub total: u64,
+    pub num_leaves: u32,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub distribution_id: u64,
+    pub index: u32,
+    pub user_id: String,
+    pub amount: u64,
+    pub claimant: Pubkey,
+}
+
+#[event]
+pub struct DistributionClawedBack {
+    pub distribution_id: u64,
+    pub amount: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ReferralConfigUpdated {
+    pub referral_bps: u16,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub user_id: String,
+    pub amount: u64,
+    pub referred_volume: u64,
+    pub to_ata: Pubkey,
+}
+
+#[event]
+pub struct AdminSetUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub version: u32,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ActionProposed {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ActionApproved {
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approval_count: u8,
+}
+
+#[event]
+pub struct ActionExecuted {
+    pub proposal_id: u64,
+    pub executed_by: Pubkey,
+}
+
+#[event]
+pub struct TreasuryPayoutExecuted {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executed_by: Pubkey,
+}
+
+#[event]
+pub struct TimelockDelayUpdated {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ActionQueued {
+    pub action_id: u64,
+    pub action: AdminAction,
+    pub eta: i64,
+    pub queued_by: Pubkey,
+}
+
+#[event]
+pub struct QueuedActionCancelled {
+    pub action_id: u64,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct QueuedActionExecuted {
+    pub action_id: u64,
+    pub executed_by: Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ProgramPauseUpdated {
+    pub paused: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct UserFreezeUpdated {
+    pub user_pda: Pubkey,
+    pub frozen: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ProposalVetoed {
+    pub proposal_id: u64,
+    pub vetoed_by: Pubkey,
+}
+
+#[event]
+pub struct SpendingLimitUpdated {
+    pub key: Pubkey,
+    pub max_per_tx: u64,
+    pub max_per_window: u64,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct AllowedDestinationAdded {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub activates_at: i64,
+    pub added_by: Pubkey,
+}
+
+#[event]
+pub struct AllowedDestinationRemoved {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub removed_by: Pubkey,
+}
+
+#[event]
+pub struct DestinationDelayUpdated {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct WalletBlocked {
+    pub wallet: Pubkey,
+    pub reason_code: u16,
+    pub blocked_by: Pubkey,
+}
+
+#[event]
+pub struct WalletUnblocked {
+    pub wallet: Pubkey,
+    pub unblocked_by: Pubkey,
+}
+
+#[event]
+pub struct KycConfigUpdated {
+    pub attester: Pubkey,
+    pub kyc_threshold: u64,
+    pub kyc_min_level: u8,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct UserAttested {
+    pub user_pda: Pubkey,
+    pub attester: Pubkey,
+    pub level: u8,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AttestationRevoked {
+    pub user_pda: Pubkey,
+    pub revoked_by: Pubkey,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub size: u64,
+}
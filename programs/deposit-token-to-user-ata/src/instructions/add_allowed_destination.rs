@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Allow treasury payouts in `mint` to token accounts owned by `owner` (only admin can do this)
+/// The destination becomes usable after `ProgramConfig::destination_delay`,
+/// leaving time for the guardian to remove an entry added with a stolen key.
+pub fn add_allowed_destination(
+    ctx: Context<AddAllowedDestination>,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let allowed_destination = &mut ctx.accounts.allowed_destination;
+    allowed_destination.mint = mint;
+    allowed_destination.owner = owner;
+    allowed_destination.added_by = ctx.accounts.admin.key();
+    allowed_destination.activates_at = now + ctx.accounts.program_config.destination_delay;
+    allowed_destination.created_at = now;
+    allowed_destination.bump = ctx.bumps.allowed_destination;
+
+    msg!("✅ Payout destination {} allowed from {}", owner, allowed_destination.activates_at);
+
+    emit!(AllowedDestinationAdded {
+        mint,
+        owner,
+        activates_at: allowed_destination.activates_at,
+        added_by: allowed_destination.added_by,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, owner: Pubkey)]
+pub struct AddAllowedDestination<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AllowedDestination::SPACE,
+        seeds = [b"allowed_destination", mint.as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
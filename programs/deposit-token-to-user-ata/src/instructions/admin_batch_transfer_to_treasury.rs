@@ -5,6 +5,7 @@ use anchor_spl::{
 use crate::state::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::utils::{charge_spending_limit, transfer_sweep_legs, SweepAccounts};
 
 
 /// **NEW FUNCTION: Batch Admin Transfer to Treasury**
@@ -16,6 +17,14 @@ use crate::events::*;
 ///
 /// Example `remaining_accounts` structure:
 /// [user_pda_1, user_ata_1, user_pda_2, user_ata_2, ...]
+///
+/// With sweep splits configured, the ATAs of the non-treasury split recipients
+/// follow the user pairs, in config order, and are shared by every user:
+/// [user_pda_1, user_ata_1, ..., split_ata_1, split_ata_2, ...]
+///
+/// Users with a lock (`has_lock`) also pass their `Lock` account right after
+/// their ATA, and only their unlocked balance can be swept:
+/// [user_pda_1, user_ata_1, lock_1, user_pda_2, user_ata_2, ...]
 pub fn admin_batch_transfer_to_treasury<'info>(
     ctx: Context<'_, '_, 'info, 'info, AdminBatchTransferToTreasury<'info>>, // CHANGE IS HERE
     user_ids: Vec<String>,
@@ -25,7 +34,16 @@ pub fn admin_batch_transfer_to_treasury<'info>(
     require!(!user_ids.is_empty(), ErrorCode::EmptyUserList);
     require!(user_ids.len() <= 5, ErrorCode::TooManyUsers); // Limit for safety and compute units
     require!(user_ids.len() == amounts.len(), ErrorCode::MismatchedArrayLengths);
-    require!(ctx.remaining_accounts.len() == user_ids.len() * 2, ErrorCode::InvalidAccountList);
+    let treasury_key = ctx.accounts.treasury_state.key();
+    let split_account_count = ctx.accounts.program_config.external_sweep_leg_count(treasury_key);
+    require!(
+        ctx.remaining_accounts.len() >= user_ids.len() * 2 + split_account_count,
+        ErrorCode::InvalidAccountList
+    );
+    let (user_accounts, split_destinations) = ctx
+        .remaining_accounts
+        .split_at(ctx.remaining_accounts.len() - split_account_count);
+    let now = Clock::get()?.unix_timestamp;
 
     // Verify admin authorization
     require!(
@@ -41,7 +59,7 @@ pub fn admin_batch_transfer_to_treasury<'info>(
 
     // Fix the lifetime issue by explicitly typing the iterator.
     // The `AccountInfo`s in `remaining_accounts` have the 'info lifetime.
-    let mut remaining_accounts_iter: std::slice::Iter<'info, AccountInfo<'info>> = ctx.remaining_accounts.iter();
+    let mut remaining_accounts_iter: std::slice::Iter<'info, AccountInfo<'info>> = user_accounts.iter();
     let mut total_transferred_amount: u64 = 0; // Fix: Add explicit type
 
     // --- 2. Iterate and Transfer for Each User ---
@@ -71,6 +89,7 @@ pub fn admin_batch_transfer_to_treasury<'info>(
         require!(user_pda_info.key() == expected_pda_key, ErrorCode::InvalidPDA);
         require!(user_pda_account.bump == pda_bump, ErrorCode::InvalidPDA);
         require!(user_pda_account.token_account == user_pda_ata_info.key(), ErrorCode::InvalidUserATA);
+        require!(!user_pda_account.frozen, ErrorCode::AccountFrozen);
 
         // Validate user_pda_ata mint and authority
         require!(user_pda_ata_account.mint == ctx.accounts.mint.key(), ErrorCode::InvalidTokenMint);
@@ -79,6 +98,25 @@ pub fn admin_batch_transfer_to_treasury<'info>(
         // Check if user has sufficient balance
         require!(user_pda_ata_account.amount >= amount_to_transfer, ErrorCode::InsufficientBalance);
 
+        // Locked funds stay put; the lock follows the ATA when the user has one
+        let lock_account: Option<Account<'info, Lock>> = if user_pda_account.has_lock {
+            let lock_info = remaining_accounts_iter.next().ok_or(ErrorCode::InvalidAccountList)?;
+            let (expected_lock_key, _) = Pubkey::find_program_address(
+                &[b"lock", user_pda_info.key().as_ref()],
+                ctx.program_id,
+            );
+            require!(lock_info.key() == expected_lock_key, ErrorCode::InvalidPDA);
+            Some(Account::try_from(lock_info)?)
+        } else {
+            None
+        };
+        let available = user_pda_account.available_balance(
+            lock_account.as_deref(),
+            user_pda_ata_account.amount,
+            now,
+        )?;
+        require!(amount_to_transfer <= available, ErrorCode::FundsLocked);
+
         // Create signer seeds for PDA
         let user_id_bytes = user_id.as_bytes();
         let seeds = &[
@@ -88,18 +126,17 @@ pub fn admin_batch_transfer_to_treasury<'info>(
         ];
         let signer_seeds = &[&seeds[..]];
 
-        // Prepare CPI accounts for token transfer
-        let cpi_accounts = anchor_spl::token::Transfer {
+        // Perform the transfer, split between the configured recipients
+        let legs = ctx.accounts.program_config.sweep_legs(amount_to_transfer, treasury_key)?;
+        let sweep_accounts = SweepAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
             from: user_pda_ata_info.clone(),
-            to: ctx.accounts.treasury_ata.to_account_info(),
             authority: user_pda_info.clone(), // The PDA is the authority for its ATA
+            treasury_state: treasury_key,
+            treasury_ata: ctx.accounts.treasury_ata.to_account_info(),
+            mint: ctx.accounts.mint.key(),
         };
-
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-        // Perform the transfer
-        anchor_spl::token::transfer(cpi_ctx, amount_to_transfer)?;
+        let paid_legs = transfer_sweep_legs(&sweep_accounts, legs, split_destinations, signer_seeds)?;
 
         total_transferred_amount = total_transferred_amount
             .checked_add(amount_to_transfer)
@@ -108,6 +145,14 @@ pub fn admin_batch_transfer_to_treasury<'info>(
         msg!("✅ Transferred {} tokens from user '{}' (ATA: {}) to treasury",
                  amount_to_transfer, user_id, user_pda_ata_info.key());
 
+        if ctx.accounts.program_config.sweep_split_count > 0 {
+            emit!(SweepSplitApplied {
+                user_id: user_id.clone(),
+                amount: amount_to_transfer,
+                legs: paid_legs,
+            });
+        }
+
         // Emit an event for each individual transfer
         emit!(AdminTransferredToTreasury {
                 user_id: user_id.clone(),
@@ -123,6 +168,11 @@ pub fn admin_batch_transfer_to_treasury<'info>(
             });
     }
 
+    require!(remaining_accounts_iter.next().is_none(), ErrorCode::InvalidAccountList);
+
+    // The whole batch counts as one transaction against the sweeper's limit
+    charge_spending_limit(&ctx.accounts.spending_limit, total_transferred_amount, now)?;
+
     msg!("🎉 Batch transfer completed. Total transferred: {}", total_transferred_amount);
 
     Ok(())
@@ -146,6 +196,14 @@ pub struct AdminBatchTransferToTreasury<'info> {
     )]
     pub treasury_state: Account<'info, TreasuryState>,
 
+    /// Holds the sweep split rules
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
@@ -164,7 +222,17 @@ pub struct AdminBatchTransferToTreasury<'info> {
     )]
     pub admin: Signer<'info>,
 
+    /// Sweeper's `SpendingLimit`. It must exist: a key without one is refused
+    /// with `SpendingLimitRequired`
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", admin.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>, // Needed for account deserialization within loop
-    // `remaining_accounts` will contain pairs of (UserPDA, TokenAccount)
+    // `remaining_accounts` will contain pairs of (UserPDA, TokenAccount) (plus Lock where needed), then split destination ATAs
 }
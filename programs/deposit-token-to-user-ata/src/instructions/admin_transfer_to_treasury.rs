@@ -5,9 +5,13 @@ use anchor_spl::{
 use crate::state::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
+use crate::utils::{charge_spending_limit, transfer_sweep_legs, SweepAccounts};
 
-pub fn admin_transfer_to_treasury(
-    ctx: Context<AdminTransferToTreasury>,
+/// Sweep tokens from a user's PDA ATA. With sweep splits configured the amount
+/// is divided between the split recipients; the ATAs of every non-treasury
+/// recipient are passed in `remaining_accounts` in config order.
+pub fn admin_transfer_to_treasury<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AdminTransferToTreasury<'info>>,
     user_id: String,
     amount: u64,
 ) -> Result<()> {
@@ -45,6 +49,16 @@ pub fn admin_transfer_to_treasury(
         ErrorCode::InsufficientBalance
     );
 
+    // Locked funds stay put
+    let available = ctx.accounts.user_pda.available_balance(
+        ctx.accounts.lock.as_deref(),
+        ctx.accounts.user_pda_ata.amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    require!(amount <= available, ErrorCode::FundsLocked);
+
+    charge_spending_limit(&ctx.accounts.spending_limit, amount, Clock::get()?.unix_timestamp)?;
+
     // Create signer seeds for PDA
     let user_id_bytes = user_id.as_bytes();
     let seeds = &[
@@ -54,21 +68,35 @@ pub fn admin_transfer_to_treasury(
     ];
     let signer_seeds = &[&seeds[..]];
 
-    // Transfer tokens from user's PDA ATA to treasury ATA
-    let cpi_accounts = anchor_spl::token::Transfer {
+    let treasury_key = ctx.accounts.treasury_state.key();
+    require!(
+        ctx.remaining_accounts.len() == ctx.accounts.program_config.external_sweep_leg_count(treasury_key),
+        ErrorCode::InvalidAccountList
+    );
+
+    // Transfer tokens from user's PDA ATA to treasury ATA (and split recipients)
+    let legs = ctx.accounts.program_config.sweep_legs(amount, treasury_key)?;
+    let sweep_accounts = SweepAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
         from: ctx.accounts.user_pda_ata.to_account_info(),
-        to: ctx.accounts.treasury_ata.to_account_info(),
         authority: ctx.accounts.user_pda.to_account_info(),
+        treasury_state: treasury_key,
+        treasury_ata: ctx.accounts.treasury_ata.to_account_info(),
+        mint: ctx.accounts.mint.key(),
     };
-
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-    anchor_spl::token::transfer(cpi_ctx, amount)?;
+    let paid_legs = transfer_sweep_legs(&sweep_accounts, legs, ctx.remaining_accounts, signer_seeds)?;
 
     msg!("✅ Admin transferred {} tokens from user '{}' to treasury", amount, user_id);
     msg!("📊 User remaining balance: {}", ctx.accounts.user_pda_ata.amount - amount);
 
+    if ctx.accounts.program_config.sweep_split_count > 0 {
+        emit!(SweepSplitApplied {
+            user_id: user_id.clone(),
+            amount,
+            legs: paid_legs,
+        });
+    }
+
     emit!(AdminTransferredToTreasury {
         user_id,
         amount,
@@ -98,13 +126,29 @@ pub struct AdminTransferToTreasury<'info> {
     )]
     pub treasury_state: Account<'info, TreasuryState>,
 
+    /// Holds the sweep split rules
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
     #[account(
         seeds = [b"deposit", user_id.as_bytes()],
         bump = user_pda.bump,
         constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
     )]
     pub user_pda: Account<'info, UserPDA>,
 
+    /// User's lock, required when `user_pda.has_lock`
+    #[account(
+        seeds = [b"lock", user_pda.key().as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
@@ -131,5 +175,15 @@ pub struct AdminTransferToTreasury<'info> {
     )]
     pub admin: Signer<'info>,
 
+    /// Sweeper's `SpendingLimit`. It must exist: a key without one is refused
+    /// with `SpendingLimitRequired`
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", admin.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
 }
\ No newline at end of file
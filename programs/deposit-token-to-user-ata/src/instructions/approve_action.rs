@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Approve an open proposal (only admin set members can do this)
+pub fn approve_action(ctx: Context<ApproveAction>, proposal_id: u64) -> Result<()> {
+    let admin_set = &ctx.accounts.admin_set;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+    require!(Clock::get()?.unix_timestamp < proposal.expires_at, ErrorCode::ProposalExpired);
+    require!(proposal.set_version == admin_set.version, ErrorCode::StaleProposal);
+
+    let index = admin_set.member_index(&ctx.accounts.approver.key())?;
+    proposal.approve(index)?;
+
+    msg!("✅ Proposal {} approved ({}/{})", proposal_id, proposal.approval_count, admin_set.threshold);
+
+    emit!(ActionApproved {
+        proposal_id,
+        approver: ctx.accounts.approver.key(),
+        approval_count: proposal.approval_count,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveAction<'info> {
+    #[account(
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub approver: Signer<'info>,
+}
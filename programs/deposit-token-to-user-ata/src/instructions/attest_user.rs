@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Write or renew a user's KYC attestation (only the configured attester can do this)
+pub fn attest_user(
+    ctx: Context<AttestUser>,
+    user_id: String,
+    level: u8,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(level > 0 && expires_at > now, ErrorCode::InvalidAttestation);
+
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.user_pda = ctx.accounts.user_pda.key();
+    attestation.attester = ctx.accounts.attester.key();
+    attestation.level = level;
+    attestation.issued_at = now;
+    attestation.expires_at = expires_at;
+    attestation.revoked = false;
+    attestation.bump = ctx.bumps.attestation;
+
+    msg!("🪪 User '{}' attested at level {} until {}", user_id, level, expires_at);
+
+    emit!(UserAttested {
+        user_pda: attestation.user_pda,
+        attester: attestation.attester,
+        level,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct AttestUser<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Attestation::SPACE,
+        seeds = [b"attestation", user_pda.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        constraint = attester.key() == program_config.attester @ ErrorCode::UnauthorizedAttester
+    )]
+    pub attester: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Block deposits signed by `wallet` (only admin can do this)
+pub fn block_wallet(ctx: Context<BlockWallet>, wallet: Pubkey, reason_code: u16) -> Result<()> {
+    let blocked_wallet = &mut ctx.accounts.blocked_wallet;
+    blocked_wallet.wallet = wallet;
+    blocked_wallet.reason_code = reason_code;
+    blocked_wallet.blocked_by = ctx.accounts.admin.key();
+    blocked_wallet.created_at = Clock::get()?.unix_timestamp;
+    blocked_wallet.bump = ctx.bumps.blocked_wallet;
+
+    msg!("⛔ Wallet {} blocked (reason {})", wallet, reason_code);
+
+    emit!(WalletBlocked {
+        wallet,
+        reason_code,
+        blocked_by: blocked_wallet.blocked_by,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlockWallet<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = BlockedWallet::SPACE,
+        seeds = [b"blocked", wallet.as_ref()],
+        bump
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
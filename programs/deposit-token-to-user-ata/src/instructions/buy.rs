@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Buy sale tokens for a user deposit account
+/// Payment moves from the buyer's token account into the treasury and the
+/// purchased tokens are minted into the user PDA's ATA for the sale mint.
+pub fn buy(
+    ctx: Context<Buy>,
+    user_id: String,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let now = Clock::get()?.unix_timestamp;
+    let sale = &ctx.accounts.sale;
+    require!(sale.status == SaleStatus::Active, ErrorCode::SaleNotActive);
+    require!(now >= sale.start_time && now < sale.end_time, ErrorCode::SaleNotActive);
+
+    let sold = sale.sold.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(sold <= sale.hard_cap, ErrorCode::SaleHardCapExceeded);
+
+    let cost = sale.cost(amount, ctx.accounts.sale_mint.decimals)?;
+    require!(cost > 0, ErrorCode::InvalidAmount);
+    let raised = sale.raised.checked_add(cost).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Take payment into the treasury
+    let cpi_accounts = anchor_spl::token::Transfer {
+        from: ctx.accounts.buyer_payment_account.to_account_info(),
+        to: ctx.accounts.treasury_ata.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    anchor_spl::token::transfer(CpiContext::new(cpi_program, cpi_accounts), cost)?;
+
+    // Mint the purchased tokens into the user's deposit account
+    let sale_mint_key = ctx.accounts.sale_mint.key();
+    let seeds = &[
+        b"sale",
+        sale_mint_key.as_ref(),
+        &[sale.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = anchor_spl::token::MintTo {
+        mint: ctx.accounts.sale_mint.to_account_info(),
+        to: ctx.accounts.user_sale_ata.to_account_info(),
+        authority: ctx.accounts.sale.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    anchor_spl::token::mint_to(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds), amount)?;
+
+    let sale_key = ctx.accounts.sale.key();
+    let sale = &mut ctx.accounts.sale;
+    sale.sold = sold;
+    sale.raised = raised;
+
+    let purchase = &mut ctx.accounts.purchase;
+    if purchase.sale == Pubkey::default() {
+        purchase.sale = sale_key;
+        purchase.user_pda = ctx.accounts.user_pda.key();
+        purchase.bump = ctx.bumps.purchase;
+    }
+    purchase.tokens = purchase.tokens.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+    purchase.paid = purchase.paid.checked_add(cost).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("✅ User '{}' bought {} tokens for {}", user_id, amount, cost);
+
+    emit!(SaleTokensPurchased {
+        user_id,
+        sale: sale_key,
+        buyer: ctx.accounts.buyer.key(),
+        amount,
+        cost,
+        user_sale_ata: ctx.accounts.user_sale_ata.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct Buy<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"sale", sale_mint.key().as_ref()],
+        bump = sale.bump,
+        has_one = payment_mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub sale: Box<Account<'info, Sale>>,
+
+    #[account(
+        mut,
+        address = sale.mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub sale_mint: Box<Account<'info, Mint>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"treasury", payment_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Box<Account<'info, TreasuryState>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Box<Account<'info, UserPDA>>,
+
+    /// User PDA's ATA for the sale token
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = sale_mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_sale_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = buyer,
+    )]
+    pub buyer_payment_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = SalePurchase::SPACE,
+        seeds = [b"purchase", sale.key().as_ref(), user_pda.key().as_ref()],
+        bump
+    )]
+    pub purchase: Box<Account<'info, SalePurchase>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
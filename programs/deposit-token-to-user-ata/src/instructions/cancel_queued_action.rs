@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Cancel a queued action before it runs (admin or guardian)
+/// The account is closed and its rent returned to whoever queued it.
+pub fn cancel_queued_action(ctx: Context<CancelQueuedAction>, action_id: u64) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let is_guardian = ctx
+        .accounts
+        .guardian_state
+        .as_ref()
+        .is_some_and(|guardian_state| guardian_state.guardian == authority);
+    require!(
+        authority == ctx.accounts.admin_state.admin || is_guardian,
+        ErrorCode::UnauthorizedAdmin
+    );
+
+    msg!("🚫 Queued action {} cancelled", action_id);
+
+    emit!(QueuedActionCancelled {
+        action_id,
+        cancelled_by: authority,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct CancelQueuedAction<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    /// Required when the guardian is cancelling
+    #[account(
+        seeds = [b"guardian"],
+        bump = guardian_state.bump,
+    )]
+    pub guardian_state: Option<Account<'info, GuardianState>>,
+
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [b"queued_action", action_id.to_le_bytes().as_ref()],
+        bump = queued_action.bump,
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    /// CHECK: Receives the queued action rent, checked by address
+    #[account(
+        mut,
+        address = queued_action.queued_by,
+    )]
+    pub queued_by: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{charge_spending_limit, pda_transfer};
+
+/// Cancel a subscription (only the deposit account owner can do this)
+/// The unused part of the current period and any carried credit are refunded
+/// from the treasury into the user's deposit ATA, and the subscription account
+/// is closed so the user can subscribe again later. The refund counts against
+/// the treasury's spending limit.
+pub fn cancel_subscription(
+    ctx: Context<CancelSubscription>,
+    user_id: String,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let subscription = &ctx.accounts.subscription;
+
+    let prorated_credit = subscription.prorated_credit(&ctx.accounts.plan, now);
+    let refund = subscription
+        .credit
+        .checked_add(prorated_credit)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    if refund > 0 {
+        charge_spending_limit(&ctx.accounts.spending_limit, refund, now)?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"treasury",
+            mint_key.as_ref(),
+            &[ctx.accounts.treasury_state.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        pda_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.treasury_ata.to_account_info(),
+            ctx.accounts.user_pda_ata.to_account_info(),
+            ctx.accounts.treasury_state.to_account_info(),
+            signer_seeds,
+            refund,
+        )?;
+    }
+
+    msg!("✅ Subscription for user '{}' cancelled, refunded {}", user_id, refund);
+
+    emit!(SubscriptionCancelled {
+        user_id,
+        subscription: subscription.key(),
+        plan: subscription.plan,
+        refunded: refund,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct CancelSubscription<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = plan.mint == mint.key() @ ErrorCode::InvalidTokenMint,
+    )]
+    pub plan: Account<'info, Plan>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"subscription", user_pda.key().as_ref()],
+        bump = subscription.bump,
+        has_one = plan @ ErrorCode::InvalidPlan,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", treasury_state.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
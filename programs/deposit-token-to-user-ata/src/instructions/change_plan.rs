@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{charge_spending_limit, pda_transfer};
+
+/// Move an active subscription to another plan (only the deposit account owner can do this)
+///
+/// The unused part of the current period (plus any carried credit) is credited
+/// against the new plan's first period, which starts now. A shortfall is charged
+/// from the user's deposit ATA, out of funds that are not locked; a surplus is
+/// either refunded from the treasury into the deposit ATA or carried forward to
+/// future charges. A refunded surplus counts against the treasury's spending limit.
+pub fn change_plan(
+    ctx: Context<ChangePlan>,
+    user_id: String,
+    refund_surplus: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let subscription = &ctx.accounts.subscription;
+    let old_plan = &ctx.accounts.plan;
+    let new_plan = &ctx.accounts.new_plan;
+
+    require!(
+        subscription.status == SubscriptionStatus::Active,
+        ErrorCode::SubscriptionInactive
+    );
+    require!(old_plan.key() != new_plan.key(), ErrorCode::SamePlan);
+
+    let prorated_credit = subscription.prorated_credit(old_plan, now);
+    let credit = subscription
+        .credit
+        .checked_add(prorated_credit)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let mut charged: u64 = 0;
+    let mut refunded: u64 = 0;
+    let mut carried_forward: u64 = 0;
+
+    if credit >= new_plan.price {
+        let surplus = credit - new_plan.price;
+        if refund_surplus && surplus > 0 {
+            // Treasury pays the surplus back into the user's deposit account
+            charge_spending_limit(&ctx.accounts.spending_limit, surplus, now)?;
+
+            let mint_key = ctx.accounts.mint.key();
+            let seeds = &[
+                b"treasury",
+                mint_key.as_ref(),
+                &[ctx.accounts.treasury_state.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            pda_transfer(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.treasury_ata.to_account_info(),
+                ctx.accounts.user_pda_ata.to_account_info(),
+                ctx.accounts.treasury_state.to_account_info(),
+                signer_seeds,
+                surplus,
+            )?;
+            refunded = surplus;
+        } else {
+            carried_forward = surplus;
+        }
+    } else {
+        charged = new_plan.price - credit;
+        require!(
+            ctx.accounts.user_pda_ata.amount >= charged,
+            ErrorCode::InsufficientBalance
+        );
+        let available = ctx.accounts.user_pda.available_balance(
+            ctx.accounts.lock.as_deref(),
+            ctx.accounts.user_pda_ata.amount,
+            now,
+        )?;
+        require!(charged <= available, ErrorCode::FundsLocked);
+
+        let user_id_bytes = user_id.as_bytes();
+        let seeds = &[
+            b"deposit",
+            user_id_bytes,
+            &[ctx.accounts.user_pda.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        pda_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.user_pda_ata.to_account_info(),
+            ctx.accounts.treasury_ata.to_account_info(),
+            ctx.accounts.user_pda.to_account_info(),
+            signer_seeds,
+            charged,
+        )?;
+    }
+
+    let old_plan_key = old_plan.key();
+    let new_plan_key = new_plan.key();
+    let new_period = new_plan.period;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.plan = new_plan_key;
+    subscription.credit = carried_forward;
+    subscription.last_charged_at = now;
+    subscription.next_charge_at = now
+        .checked_add(new_period)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("✅ User '{}' moved to plan {} (charged {}, refunded {}, carried {})",
+         user_id, new_plan_key, charged, refunded, carried_forward);
+
+    emit!(SubscriptionPlanChanged {
+        user_id,
+        subscription: subscription.key(),
+        old_plan: old_plan_key,
+        new_plan: new_plan_key,
+        prorated_credit,
+        charged,
+        refunded,
+        carried_forward,
+        next_charge_at: subscription.next_charge_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct ChangePlan<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    /// User's lock, required when `user_pda.has_lock`
+    #[account(
+        seeds = [b"lock", user_pda.key().as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+
+    #[account(
+        constraint = plan.mint == mint.key() @ ErrorCode::InvalidTokenMint,
+    )]
+    pub plan: Account<'info, Plan>,
+
+    #[account(
+        constraint = new_plan.mint == mint.key() @ ErrorCode::InvalidTokenMint,
+    )]
+    pub new_plan: Account<'info, Plan>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", user_pda.key().as_ref()],
+        bump = subscription.bump,
+        has_one = plan @ ErrorCode::InvalidPlan,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub owner: Signer<'info>,
+
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", treasury_state.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
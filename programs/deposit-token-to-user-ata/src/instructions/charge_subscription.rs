@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::pda_transfer;
+
+/// Collect a due subscription period from the user's deposit ATA into the treasury
+/// (only admin can do this). Carried-forward credit is applied first. If the
+/// deposit account cannot cover the rest the subscription is marked lapsed and
+/// the charge can be retried until the plan's grace period ends, after which
+/// it becomes inactive. Locked funds do not count towards the balance.
+pub fn charge_subscription(
+    ctx: Context<ChargeSubscription>,
+    user_id: String,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let plan = &ctx.accounts.plan;
+    let subscription = &ctx.accounts.subscription;
+
+    require!(
+        subscription.status != SubscriptionStatus::Inactive,
+        ErrorCode::SubscriptionInactive
+    );
+    require!(now >= subscription.next_charge_at, ErrorCode::SubscriptionNotDue);
+
+    let amount_due = plan.price.saturating_sub(subscription.credit);
+    let balance = ctx.accounts.user_pda.available_balance(
+        ctx.accounts.lock.as_deref(),
+        ctx.accounts.user_pda_ata.amount,
+        now,
+    )?;
+    if balance < amount_due {
+        let grace_ends_at = subscription
+            .next_charge_at
+            .checked_add(plan.grace_period)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.status = if now >= grace_ends_at {
+            SubscriptionStatus::Inactive
+        } else {
+            SubscriptionStatus::Lapsed
+        };
+
+        msg!("⚠️ Subscription for user '{}' lapsed: balance {} < due {}", user_id, balance, amount_due);
+
+        emit!(SubscriptionLapsed {
+            user_id,
+            subscription: subscription.key(),
+            plan: subscription.plan,
+            amount_due,
+            balance,
+            grace_ends_at,
+            inactive: subscription.status == SubscriptionStatus::Inactive,
+        });
+
+        return Ok(());
+    }
+
+    let user_id_bytes = user_id.as_bytes();
+    let seeds = &[
+        b"deposit",
+        user_id_bytes,
+        &[ctx.accounts.user_pda.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if amount_due > 0 {
+        pda_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.user_pda_ata.to_account_info(),
+            ctx.accounts.treasury_ata.to_account_info(),
+            ctx.accounts.user_pda.to_account_info(),
+            signer_seeds,
+            amount_due,
+        )?;
+    }
+
+    let price = plan.price;
+    let period = plan.period;
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.credit = subscription.credit.saturating_sub(price);
+    subscription.status = SubscriptionStatus::Active;
+    subscription.last_charged_at = now;
+    subscription.next_charge_at = subscription
+        .next_charge_at
+        .checked_add(period)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("✅ Charged {} from user '{}', next charge at {}", amount_due, user_id, subscription.next_charge_at);
+
+    emit!(SubscriptionCharged {
+        user_id,
+        subscription: subscription.key(),
+        plan: subscription.plan,
+        amount: amount_due,
+        credit_applied: price - amount_due,
+        next_charge_at: subscription.next_charge_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct ChargeSubscription<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    /// User's lock, required when `user_pda.has_lock`
+    #[account(
+        seeds = [b"lock", user_pda.key().as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+
+    #[account(
+        constraint = plan.mint == mint.key() @ ErrorCode::InvalidTokenMint,
+    )]
+    pub plan: Account<'info, Plan>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", user_pda.key().as_ref()],
+        bump = subscription.bump,
+        has_one = plan @ ErrorCode::InvalidPlan,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{distribution_leaf, pda_transfer, verify_merkle_proof};
+
+/// Claim a distribution leaf into the user's deposit ATA
+/// The leaf is `keccak(index_le, user_id, amount_le)` and proof pairs are hashed
+/// in sorted order. Anyone can submit a claim; the funds always land in the
+/// user PDA's ATA.
+pub fn claim(
+    ctx: Context<Claim>,
+    distribution_id: u64,
+    index: u32,
+    user_id: String,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let distribution = &ctx.accounts.distribution;
+    require!(!distribution.clawed_back, ErrorCode::DistributionExpired);
+    require!(Clock::get()?.unix_timestamp < distribution.expires_at, ErrorCode::DistributionExpired);
+
+    let leaf = distribution_leaf(index, &user_id, amount);
+    require!(
+        verify_merkle_proof(&proof, &distribution.merkle_root, leaf),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.set_claimed(index)?;
+    distribution.claimed = distribution
+        .claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(distribution.claimed <= distribution.total, ErrorCode::InsufficientBalance);
+
+    // Distribution PDA signs for its vault
+    let distribution_id_bytes = distribution_id.to_le_bytes();
+    let seeds = &[
+        b"distribution",
+        distribution_id_bytes.as_ref(),
+        &[distribution.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.user_pda_ata.to_account_info(),
+        ctx.accounts.distribution.to_account_info(),
+        signer_seeds,
+        amount,
+    )?;
+
+    msg!("🎁 User '{}' claimed {} tokens from distribution {}", user_id, amount, distribution_id);
+
+    emit!(RewardClaimed {
+        distribution_id,
+        index,
+        user_id,
+        amount,
+        claimant: ctx.accounts.claimant.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(distribution_id: u64, index: u32, user_id: String)]
+pub struct Claim<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"distribution", distribution_id.to_le_bytes().as_ref()],
+        bump = distribution.bump,
+        has_one = mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = distribution,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{charge_spending_limit, pda_transfer};
+
+/// Pay a referrer's accrued rewards from the treasury into their deposit ATA
+/// (only the referrer's deposit account owner can do this). Claims count
+/// against the treasury's spending limit.
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>, user_id: String) -> Result<()> {
+    let amount = ctx.accounts.user_pda.referral_rewards;
+    require!(amount > 0, ErrorCode::NoReferralRewards);
+    require!(ctx.accounts.treasury_ata.amount >= amount, ErrorCode::InsufficientBalance);
+    charge_spending_limit(&ctx.accounts.spending_limit, amount, Clock::get()?.unix_timestamp)?;
+
+    // Treasury PDA signs for its ATA
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        b"treasury",
+        mint_key.as_ref(),
+        &[ctx.accounts.treasury_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        ctx.accounts.user_pda_ata.to_account_info(),
+        ctx.accounts.treasury_state.to_account_info(),
+        signer_seeds,
+        amount,
+    )?;
+
+    let user_pda = &mut ctx.accounts.user_pda;
+    user_pda.referral_rewards = 0;
+    user_pda.referral_rewards_claimed = user_pda
+        .referral_rewards_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("🤝 User '{}' claimed {} in referral rewards", user_id, amount);
+
+    emit!(ReferralRewardsClaimed {
+        user_id,
+        amount,
+        referred_volume: user_pda.referred_volume,
+        to_ata: ctx.accounts.user_pda_ata.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub owner: Signer<'info>,
+
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", treasury_state.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
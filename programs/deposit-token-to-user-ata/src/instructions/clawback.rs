@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::pda_transfer;
+
+/// Return the unclaimed part of an expired distribution to the treasury
+/// (only admin can do this)
+pub fn clawback(ctx: Context<Clawback>, distribution_id: u64) -> Result<()> {
+    let distribution = &ctx.accounts.distribution;
+    require!(!distribution.clawed_back, ErrorCode::DistributionAlreadyClawedBack);
+    require!(
+        Clock::get()?.unix_timestamp >= distribution.expires_at,
+        ErrorCode::DistributionNotExpired
+    );
+
+    let amount = ctx.accounts.vault.amount;
+    if amount > 0 {
+        let distribution_id_bytes = distribution_id.to_le_bytes();
+        let seeds = &[
+            b"distribution",
+            distribution_id_bytes.as_ref(),
+            &[distribution.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        pda_transfer(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.treasury_ata.to_account_info(),
+            ctx.accounts.distribution.to_account_info(),
+            signer_seeds,
+            amount,
+        )?;
+    }
+
+    ctx.accounts.distribution.clawed_back = true;
+
+    msg!("↩️ Clawed back {} unclaimed tokens from distribution {}", amount, distribution_id);
+
+    emit!(DistributionClawedBack {
+        distribution_id,
+        amount,
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(distribution_id: u64)]
+pub struct Clawback<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"distribution", distribution_id.to_le_bytes().as_ref()],
+        bump = distribution.bump,
+        has_one = mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = distribution,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
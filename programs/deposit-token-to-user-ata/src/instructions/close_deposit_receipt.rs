@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Close a nonce receipt once its retention window has passed and return
+/// the rent to the original depositor. Reference receipts are permanent.
+pub fn close_deposit_receipt(ctx: Context<CloseDepositReceipt>) -> Result<()> {
+    let receipt = &ctx.accounts.deposit_receipt;
+    let now = Clock::get()?.unix_timestamp;
+
+    let closable_at = receipt
+        .created_at
+        .checked_add(DepositReceipt::RETENTION_PERIOD)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(now >= closable_at, ErrorCode::ReceiptRetentionActive);
+
+    msg!("🧾 Closed deposit receipt {} (nonce {})", receipt.key(), receipt.nonce);
+
+    emit!(DepositReceiptClosed {
+        receipt: receipt.key(),
+        user_pda: receipt.user_pda,
+        depositor: receipt.depositor,
+        nonce: receipt.nonce,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseDepositReceipt<'info> {
+    #[account(
+        mut,
+        close = depositor,
+        seeds = [b"receipt", deposit_receipt.user_pda.as_ref(), &deposit_receipt.nonce.to_le_bytes()],
+        bump = deposit_receipt.bump,
+        has_one = depositor @ ErrorCode::UnauthorizedReceiptClose,
+    )]
+    pub deposit_receipt: Account<'info, DepositReceipt>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Hand admin authority to an M-of-N admin set (only admin can do this)
+/// From then on privileged actions run through `propose_action` /
+/// `approve_action` / `execute_action` and the direct admin instructions
+/// for them are rejected.
+pub fn configure_admin_set(
+    ctx: Context<ConfigureAdminSet>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let admin_set = &mut ctx.accounts.admin_set;
+    admin_set.set_members(members, threshold)?;
+    admin_set.bump = ctx.bumps.admin_set;
+
+    ctx.accounts.admin_state.multisig_enabled = true;
+
+    msg!("✅ Admin set configured: {}-of-{}", admin_set.threshold, admin_set.members.len());
+
+    emit!(AdminSetUpdated {
+        members: admin_set.members.clone(),
+        threshold: admin_set.threshold,
+        version: admin_set.version,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureAdminSet<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AdminSet::SPACE,
+        seeds = [b"admin_set"],
+        bump
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
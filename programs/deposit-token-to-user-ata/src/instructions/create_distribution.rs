@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{charge_spending_limit, pda_transfer};
+
+/// Fund a Merkle distribution vault from the treasury (only admin can do this)
+/// Users claim their leaf with `claim`; whatever is left after `expires_at`
+/// can be returned to the treasury with `clawback`. The funding counts
+/// against the treasury's spending limit.
+pub fn create_distribution(
+    ctx: Context<CreateDistribution>,
+    distribution_id: u64,
+    merkle_root: [u8; 32],
+    total: u64,
+    num_leaves: u32,
+    expires_at: i64,
+) -> Result<()> {
+    require!(total > 0, ErrorCode::InvalidAmount);
+    require!(
+        num_leaves > 0 && num_leaves <= MAX_DISTRIBUTION_LEAVES,
+        ErrorCode::InvalidDistribution
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(expires_at > now, ErrorCode::InvalidDistribution);
+    require!(ctx.accounts.treasury_ata.amount >= total, ErrorCode::InsufficientBalance);
+    charge_spending_limit(&ctx.accounts.spending_limit, total, now)?;
+
+    // Move the rewards out of the treasury into the distribution vault
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        b"treasury",
+        mint_key.as_ref(),
+        &[ctx.accounts.treasury_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.treasury_state.to_account_info(),
+        signer_seeds,
+        total,
+    )?;
+
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.distribution_id = distribution_id;
+    distribution.mint = mint_key;
+    distribution.merkle_root = merkle_root;
+    distribution.total = total;
+    distribution.claimed = 0;
+    distribution.num_leaves = num_leaves;
+    distribution.expires_at = expires_at;
+    distribution.created_at = now;
+    distribution.clawed_back = false;
+    distribution.bump = ctx.bumps.distribution;
+    distribution.claimed_bitmap = vec![0; Distribution::bitmap_len(num_leaves)];
+
+    msg!("🎁 Created distribution {}: {} tokens over {} leaves", distribution_id, total, num_leaves);
+
+    emit!(DistributionCreated {
+        distribution_id,
+        distribution: distribution.key(),
+        mint: mint_key,
+        merkle_root,
+        total,
+        num_leaves,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(distribution_id: u64, merkle_root: [u8; 32], total: u64, num_leaves: u32)]
+pub struct CreateDistribution<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Distribution::space(num_leaves),
+        seeds = [b"distribution", distribution_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    /// Distribution's own ATA (owned by the distribution PDA)
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = distribution,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", treasury_state.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Mint,
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Create a subscription plan billed in `mint` (only admin can do this)
+pub fn create_plan(
+    ctx: Context<CreatePlan>,
+    plan_id: u64,
+    price: u64,
+    period: i64,
+    grace_period: i64,
+) -> Result<()> {
+    require!(price > 0, ErrorCode::InvalidAmount);
+    require!(period > 0, ErrorCode::InvalidPlanPeriod);
+    require!(grace_period >= 0, ErrorCode::InvalidPlanPeriod);
+
+    let plan = &mut ctx.accounts.plan;
+    plan.plan_id = plan_id;
+    plan.mint = ctx.accounts.mint.key();
+    plan.price = price;
+    plan.period = period;
+    plan.grace_period = grace_period;
+    plan.created_at = Clock::get()?.unix_timestamp;
+    plan.bump = ctx.bumps.plan;
+
+    msg!("✅ Created plan {}: {} every {}s", plan_id, price, period);
+
+    emit!(PlanCreated {
+        plan_id,
+        plan: plan.key(),
+        mint: plan.mint,
+        price,
+        period,
+        grace_period,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(plan_id: u64)]
+pub struct CreatePlan<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Plan::SPACE,
+        seeds = [b"plan", plan_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub plan: Account<'info, Plan>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Subscribe a user deposit account to a plan (only the deposit account owner can do this)
+/// The first period is due immediately and is collected by `charge_subscription`.
+pub fn create_subscription(
+    ctx: Context<CreateSubscription>,
+    user_id: String,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.user_pda = ctx.accounts.user_pda.key();
+    subscription.plan = ctx.accounts.plan.key();
+    subscription.status = SubscriptionStatus::Active;
+    subscription.next_charge_at = now;
+    subscription.credit = 0;
+    subscription.last_charged_at = 0;
+    subscription.created_at = now;
+    subscription.bump = ctx.bumps.subscription;
+
+    msg!("✅ User '{}' subscribed to plan {}", user_id, ctx.accounts.plan.plan_id);
+
+    emit!(SubscriptionCreated {
+        user_id,
+        subscription: subscription.key(),
+        plan: subscription.plan,
+        next_charge_at: subscription.next_charge_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct CreateSubscription<'info> {
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    pub plan: Account<'info, Plan>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Subscription::SPACE,
+        seeds = [b"subscription", user_pda.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
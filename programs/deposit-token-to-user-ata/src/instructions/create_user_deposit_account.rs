@@ -4,14 +4,28 @@ use anchor_spl::{
     token::{Token, TokenAccount, Mint},
 };
 use crate::state::*;
+use crate::errors::ErrorCode;
 use crate::events::*;
 
 /// Create a user-specific PDA and its Associated Token Account in one transaction
 /// Returns both the PDA address and ATA address
+///
+/// Passing `referrer_id` with the referrer's `referrer_pda` attributes this
+/// user's future deposits to them.
 pub fn create_user_deposit_account(
     ctx: Context<CreateUserDepositAccount>,
-    user_id: String
+    user_id: String,
+    referrer_id: Option<String>,
 ) -> Result<CreateAccountResult> {
+    let referrer = match (&referrer_id, &ctx.accounts.referrer_pda) {
+        (Some(referrer_id), Some(referrer_pda)) => {
+            require!(*referrer_id != user_id, ErrorCode::InvalidReferrer);
+            Some(referrer_pda.key())
+        }
+        (None, None) => None,
+        _ => return err!(ErrorCode::InvalidReferrer),
+    };
+
     let user_pda = &mut ctx.accounts.user_pda;
 
     // Initialize the PDA with user data
@@ -20,6 +34,17 @@ pub fn create_user_deposit_account(
     // user_pda.bump = *ctx.bumps.get("user_pda").unwrap();
     user_pda.bump = ctx.bumps.user_pda;
     user_pda.created_at = Clock::get()?.unix_timestamp;
+    user_pda.tier = 0;
+    user_pda.window_start = user_pda.created_at;
+    user_pda.window_deposited = 0;
+    user_pda.has_lock = false;
+    user_pda.total_refunded = 0;
+    user_pda.referrer = referrer;
+    user_pda.referred_volume = 0;
+    user_pda.referral_rewards = 0;
+    user_pda.referral_rewards_claimed = 0;
+    user_pda.frozen = false;
+    user_pda.version = ACCOUNT_VERSION;
 
     // The ATA is automatically created by Anchor constraints with PDA as authority
     // Store the ATA address in the PDA for easy reference
@@ -33,6 +58,9 @@ pub fn create_user_deposit_account(
     msg!("🔑 ATA Owner: {}", ctx.accounts.user_ata.owner);
     msg!("🎯 Deposit Address (PDA): {}", pda_address);
     msg!("💰 Token Account (ATA): {}", ata_address);
+    if let Some(referrer_id) = &referrer_id {
+        msg!("🤝 Referred by '{}'", referrer_id);
+    }
 
     // Emit event
     emit!(UserDepositAccountCreated {
@@ -54,7 +82,7 @@ pub fn create_user_deposit_account(
 }
 
 #[derive(Accounts)]
-#[instruction(user_id: String)]
+#[instruction(user_id: String, referrer_id: Option<String>)]
 pub struct CreateUserDepositAccount<'info> {
     #[account(
         init,
@@ -76,6 +104,13 @@ pub struct CreateUserDepositAccount<'info> {
 
     pub subscription_token_mint: Account<'info, Mint>,
 
+    /// Referrer's deposit account, required with `referrer_id`
+    #[account(
+        seeds = [b"deposit", referrer_id.as_deref().unwrap_or_default().as_bytes()],
+        bump = referrer_pda.bump,
+    )]
+    pub referrer_pda: Option<Account<'info, UserPDA>>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
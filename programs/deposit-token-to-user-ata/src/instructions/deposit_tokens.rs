@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::{
     token::{Token, TokenAccount, Mint},
 };
@@ -7,15 +8,163 @@ use crate::errors::ErrorCode;
 use crate::events::*;
 
 
+/// Maximum memo length in bytes
+pub const MAX_MEMO_LEN: usize = 64;
+
 /// Deposit tokens to user's ATA
+///
+/// The source can be any token account of `mint` that the signer owns (their
+/// ATA being the usual case) or has been approved as delegate on.
+///
+/// `reference` and `memo` are echoed in `TokensDeposited` so the deposit can be
+/// matched to an invoice or order. Passing `reference_receipt` as well records
+/// the reference on-chain and rejects any later deposit reusing it.
+///
+/// Clients that retry on timeout pass a `nonce` with `deposit_receipt`; a replay
+/// of the same nonce fails with `DuplicateDeposit` instead of charging twice.
+///
+/// Promotional credits pass a `lock_schedule` with the `lock` account; the
+/// credited amount then stays out of reach of sweeps until it unlocks. Only the
+/// account owner, or the admin with `admin_state`, can create or extend a lock.
+#[allow(clippy::too_many_arguments)]
 pub fn deposit_tokens(
     ctx: Context<DepositTokens>,
     user_id: String,
     amount: u64,
+    reference: Option<[u8; 32]>,
+    memo: Option<String>,
+    nonce: Option<u64>,
+    lock_schedule: Option<LockSchedule>,
 ) -> Result<()> {
     require!(amount > 0, ErrorCode::InvalidAmount);
+    ctx.accounts.treasury_state.check_deposit_limits(amount)?;
+    if let Some(memo) = &memo {
+        require!(memo.len() <= MAX_MEMO_LEN, ErrorCode::MemoTooLong);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Large deposits need a KYC attestation
+    let attestation = ctx.accounts.attestation.as_deref().map(|attestation| &**attestation);
+    ctx.accounts.program_config.check_kyc(attestation, amount, now)?;
+
+    // Per-user tier caps over the rolling window
+    let limits = ctx
+        .accounts
+        .program_config
+        .tier_limits
+        .get(ctx.accounts.user_pda.tier as usize)
+        .copied()
+        .ok_or(ErrorCode::InvalidTier)?;
+    let fee_amount = ctx.accounts.program_config.deposit_fee(amount)?;
+    let credited_amount = amount - fee_amount;
+    let balance = ctx.accounts.user_pda_ata.amount;
+    ctx.accounts.user_pda.record_deposit(&limits, balance, credited_amount, now)?;
+
+    if let Some(receipt) = ctx.accounts.reference_receipt.as_mut() {
+        let reference = reference.ok_or(ErrorCode::MissingReference)?;
+        require!(receipt.created_at == 0, ErrorCode::DuplicateReference);
+
+        receipt.user_pda = ctx.accounts.user_pda.key();
+        receipt.depositor = ctx.accounts.user.key();
+        receipt.amount = amount;
+        receipt.reference = reference;
+        receipt.nonce = nonce.unwrap_or_default();
+        receipt.created_at = now;
+        receipt.bump = ctx.bumps.reference_receipt.ok_or(ErrorCode::InvalidPDA)?;
+    }
+
+    if let Some(receipt) = ctx.accounts.deposit_receipt.as_mut() {
+        let nonce = nonce.ok_or(ErrorCode::MissingNonce)?;
+        require!(receipt.created_at == 0, ErrorCode::DuplicateDeposit);
+
+        receipt.user_pda = ctx.accounts.user_pda.key();
+        receipt.depositor = ctx.accounts.user.key();
+        receipt.amount = amount;
+        receipt.reference = reference.unwrap_or_default();
+        receipt.nonce = nonce;
+        receipt.created_at = now;
+        receipt.bump = ctx.bumps.deposit_receipt.ok_or(ErrorCode::InvalidPDA)?;
+    }
+
+    if let Some(lock) = ctx.accounts.lock.as_mut() {
+        let schedule = lock_schedule.ok_or(ErrorCode::MissingLockSchedule)?;
+        // Anyone else could tie up the user's balance
+        let depositor = ctx.accounts.user.key();
+        let is_admin = ctx
+            .accounts
+            .admin_state
+            .as_ref()
+            .is_some_and(|admin_state| admin_state.admin == depositor);
+        require!(
+            depositor == ctx.accounts.user_pda.owner || is_admin,
+            ErrorCode::UnauthorizedLock
+        );
+        if lock.created_at == 0 {
+            lock.user_pda = ctx.accounts.user_pda.key();
+            lock.created_at = now;
+            lock.bump = ctx.bumps.lock.ok_or(ErrorCode::InvalidPDA)?;
+        }
+        lock.add(credited_amount, &schedule, now)?;
+        ctx.accounts.user_pda.has_lock = true;
+
+        msg!("🔒 Locked {} tokens for user '{}' until {}", credited_amount, user_id, lock.unlock_at);
+
+        emit!(TokensLocked {
+            user_id: user_id.clone(),
+            amount: credited_amount,
+            total_locked: lock.amount,
+            unlock_at: lock.unlock_at,
+            vesting_start: lock.vesting_start,
+        });
+    } else {
+        require!(lock_schedule.is_none(), ErrorCode::MissingLockAccount);
+    }
+
+    // Attribute the credited amount to the referrer, if any
+    match (ctx.accounts.user_pda.referrer, ctx.accounts.referrer_pda.as_mut()) {
+        (Some(referrer), Some(referrer_pda)) => {
+            require_keys_eq!(referrer_pda.key(), referrer, ErrorCode::InvalidReferrer);
+            let reward = ctx.accounts.program_config.referral_reward(credited_amount)?;
+            referrer_pda.referred_volume = referrer_pda
+                .referred_volume
+                .checked_add(credited_amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            referrer_pda.referral_rewards = referrer_pda
+                .referral_rewards
+                .checked_add(reward)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        (None, None) => {}
+        _ => return err!(ErrorCode::InvalidReferrer),
+    }
+
+    // A delegate can only move what the owner approved
+    let source = &ctx.accounts.user_token_account;
+    if source.owner != ctx.accounts.user.key() {
+        require!(
+            source.delegated_amount >= amount,
+            ErrorCode::InsufficientDelegatedAmount
+        );
+    }
 
-    // Transfer tokens from user's wallet to PDA's ATA
+    // Route the protocol fee to the fee vault
+    if fee_amount > 0 {
+        let fee_vault = ctx.accounts.fee_vault.as_ref().ok_or(ErrorCode::MissingFeeVault)?;
+
+        let cpi_accounts = anchor_spl::token::Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: fee_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        anchor_spl::token::transfer(cpi_ctx, fee_amount)?;
+    }
+
+    // Transfer the remainder from the source account to PDA's ATA
     let cpi_accounts = anchor_spl::token::Transfer {
         from: ctx.accounts.user_token_account.to_account_info(),
         to: ctx.accounts.user_pda_ata.to_account_info(),
@@ -25,27 +174,34 @@ pub fn deposit_tokens(
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-    anchor_spl::token::transfer(cpi_ctx, amount)?;
+    anchor_spl::token::transfer(cpi_ctx, credited_amount)?;
 
-    msg!("✅ Deposited {} tokens for user '{}'", amount, user_id);
+    msg!("✅ Deposited {} tokens for user '{}' (fee {})", credited_amount, user_id, fee_amount);
 
     // Emit event
     emit!(TokensDeposited {
         user_id,
         amount,
+        fee_amount,
         depositor: ctx.accounts.user.key(),
+        source: ctx.accounts.user_token_account.key(),
         pda_ata: ctx.accounts.user_pda_ata.key(),
+        reference,
+        memo,
+        nonce,
     });
 
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(user_id: String)]
+#[instruction(user_id: String, amount: u64, reference: Option<[u8; 32]>, memo: Option<String>, nonce: Option<u64>, lock_schedule: Option<LockSchedule>)]
 pub struct DepositTokens<'info> {
     #[account(
+        mut,
         seeds = [b"deposit", user_id.as_bytes()],
-        bump = user_pda.bump
+        bump = user_pda.bump,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
     )]
     pub user_pda: Account<'info, UserPDA>,
 
@@ -57,18 +213,101 @@ pub struct DepositTokens<'info> {
     )]
     pub user_pda_ata: Account<'info, TokenAccount>,
 
-    /// User's source token account (they own this)
+    /// Source token account (owned by the user, or delegated to them)
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = user,
+        token::mint = mint,
+        constraint = user_token_account.owner == user.key()
+            || user_token_account.delegate == COption::Some(user.key())
+            @ ErrorCode::UnauthorizedDepositSource,
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
     pub mint: Account<'info, Mint>,
 
+    /// Treasury for `mint`, holds the deposit limits
+    #[account(
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    /// Holds the per-tier deposit limits and fee settings
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Fee recipient's ATA, required while a deposit fee is configured
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = program_config.fee_recipient,
+    )]
+    pub fee_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Optional idempotency record for `reference`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ReferenceReceipt::SPACE,
+        seeds = [b"reference", user_pda.key().as_ref(), &reference.unwrap_or_default()],
+        bump
+    )]
+    pub reference_receipt: Option<Account<'info, ReferenceReceipt>>,
+
+    /// Optional retry-protection record for `nonce`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = DepositReceipt::SPACE,
+        seeds = [b"receipt", user_pda.key().as_ref(), &nonce.unwrap_or_default().to_le_bytes()],
+        bump
+    )]
+    pub deposit_receipt: Option<Account<'info, DepositReceipt>>,
+
+    /// Optional lock on the credited amount, required with `lock_schedule`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Lock::SPACE,
+        seeds = [b"lock", user_pda.key().as_ref()],
+        bump
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+
+    /// Admin state, needed when the admin locks a deposit into someone else's account
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Option<Account<'info, AdminState>>,
+
+    /// User's KYC attestation, required above `ProgramConfig::kyc_threshold`
+    #[account(
+        seeds = [b"attestation", user_pda.key().as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Option<Box<Account<'info, Attestation>>>,
+
+    /// Referrer's deposit account, required when `user_pda.referrer` is set
+    #[account(mut)]
+    pub referrer_pda: Option<Account<'info, UserPDA>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
+    /// Blocklist entry for `user`; must not exist
+    /// CHECK: Address checked by seeds; any data means the wallet is blocked
+    #[account(
+        seeds = [b"blocked", user.key().as_ref()],
+        bump,
+        constraint = blocked_wallet.data_is_empty() @ ErrorCode::DepositorBlocked,
+    )]
+    pub blocked_wallet: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
\ No newline at end of file
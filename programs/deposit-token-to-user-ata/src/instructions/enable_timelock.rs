@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Turn on the timelock (only admin can do this)
+/// From then on admin and config changes must be queued with `queue_action`
+/// and wait `delay` seconds. The delay itself can only be changed (or set
+/// back to 0) through a queued `UpdateTimelockDelay` action.
+pub fn enable_timelock(ctx: Context<EnableTimelock>, delay: i64) -> Result<()> {
+    require!(delay > 0, ErrorCode::InvalidTimelockDelay);
+    validate_timelock_delay(delay)?;
+
+    let admin_state = &mut ctx.accounts.admin_state;
+    admin_state.timelock_delay = delay;
+
+    msg!("⏳ Timelock enabled with a {}s delay", delay);
+
+    emit!(TimelockDelayUpdated {
+        old_delay: 0,
+        new_delay: delay,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EnableTimelock<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::admin_actions::{execute_admin_action, AdminActionAccounts};
+
+/// Execute a proposal once it has enough approvals (only admin set members can do this)
+/// Treasury payouts and deposit limit changes also need the treasury accounts
+/// for the action's mint; payouts need the destination and token program.
+/// Refunds need the same plus the user's PDA, with its deposit ATA as `destination`.
+/// Setting a spending limit needs its PDA and, for a new key, the system program.
+pub fn execute_action(ctx: Context<ExecuteAction>, proposal_id: u64) -> Result<()> {
+    let admin_set = &ctx.accounts.admin_set;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(ctx.accounts.admin_state.timelock_delay == 0, ErrorCode::TimelockRequired);
+
+    require!(admin_set.member_index(&ctx.accounts.executor.key()).is_ok(), ErrorCode::NotAdminSetMember);
+    require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+    require!(Clock::get()?.unix_timestamp < proposal.expires_at, ErrorCode::ProposalExpired);
+    require!(proposal.set_version == admin_set.version, ErrorCode::StaleProposal);
+    require!(proposal.approval_count >= admin_set.threshold, ErrorCode::ThresholdNotMet);
+
+    let action = proposal.action.clone();
+    ctx.accounts.proposal.executed = true;
+
+    let executed_by = ctx.accounts.executor.key();
+    let accounts = ctx.accounts;
+    execute_admin_action(
+        &action,
+        AdminActionAccounts {
+            admin_state: &mut accounts.admin_state,
+            admin_set: Some(&mut accounts.admin_set),
+            program_config: &mut accounts.program_config,
+            guardian_state: accounts.guardian_state.as_mut(),
+            treasury_state: accounts.treasury_state.as_mut(),
+            treasury_ata: accounts.treasury_ata.as_ref(),
+            destination: accounts.destination.as_ref(),
+            user_pda: accounts.user_pda.as_mut(),
+            allowed_destination: accounts.allowed_destination.as_ref(),
+            token_program: accounts.token_program.as_ref(),
+            spending_limit: accounts.spending_limit.as_ref().map(|limit| limit.as_ref()),
+            payer: Some(accounts.executor.as_ref()),
+            system_program: accounts.system_program.as_ref(),
+        },
+        executed_by,
+    )?;
+
+    msg!("⚡ Proposal {} executed by {}", proposal_id, executed_by);
+
+    emit!(ActionExecuted {
+        proposal_id,
+        executed_by,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Guardian state (guardian changes)
+    #[account(
+        mut,
+        seeds = [b"guardian"],
+        bump = guardian_state.bump,
+    )]
+    pub guardian_state: Option<Account<'info, GuardianState>>,
+
+    /// Treasury for the action's mint (payouts, deposit limits)
+    #[account(mut)]
+    pub treasury_state: Option<Account<'info, TreasuryState>>,
+
+    #[account(mut)]
+    pub treasury_ata: Option<Account<'info, TokenAccount>>,
+
+    /// Payout destination, or the user's deposit ATA for refunds
+    #[account(mut)]
+    pub destination: Option<Account<'info, TokenAccount>>,
+
+    /// User receiving a refund
+    #[account(mut)]
+    pub user_pda: Option<Account<'info, UserPDA>>,
+
+    /// Allowlist entry for the payout destination's owner
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// `SpendingLimit` PDA of the treasury (payouts) or of the key being limited
+    /// CHECK: Address and contents checked when the action runs
+    #[account(mut)]
+    pub spending_limit: Option<UncheckedAccount<'info>>,
+
+    /// Creates the `SpendingLimit` account when one is set for a new key
+    pub system_program: Option<Program<'info, System>>,
+}
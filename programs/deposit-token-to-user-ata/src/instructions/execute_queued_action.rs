@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::admin_actions::{execute_admin_action, AdminActionAccounts};
+
+/// Execute a queued action once its delay has passed (only admin can do this)
+/// The optional accounts are the same as for `execute_action`.
+pub fn execute_queued_action(ctx: Context<ExecuteQueuedAction>, action_id: u64) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.queued_action.eta,
+        ErrorCode::TimelockNotReady
+    );
+
+    let action = ctx.accounts.queued_action.action.clone();
+    let executed_by = ctx.accounts.admin.key();
+    let accounts = ctx.accounts;
+    execute_admin_action(
+        &action,
+        AdminActionAccounts {
+            admin_state: &mut accounts.admin_state,
+            admin_set: None,
+            program_config: &mut accounts.program_config,
+            guardian_state: accounts.guardian_state.as_mut(),
+            treasury_state: accounts.treasury_state.as_mut(),
+            treasury_ata: accounts.treasury_ata.as_ref(),
+            destination: accounts.destination.as_ref(),
+            user_pda: accounts.user_pda.as_mut(),
+            allowed_destination: accounts.allowed_destination.as_ref(),
+            token_program: accounts.token_program.as_ref(),
+            spending_limit: accounts.spending_limit.as_ref().map(|limit| limit.as_ref()),
+            payer: Some(accounts.admin.as_ref()),
+            system_program: accounts.system_program.as_ref(),
+        },
+        executed_by,
+    )?;
+
+    msg!("⚡ Queued action {} executed by {}", action_id, executed_by);
+
+    emit!(QueuedActionExecuted {
+        action_id,
+        executed_by,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct ExecuteQueuedAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"queued_action", action_id.to_le_bytes().as_ref()],
+        bump = queued_action.bump,
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    /// Guardian state (guardian changes)
+    #[account(
+        mut,
+        seeds = [b"guardian"],
+        bump = guardian_state.bump,
+    )]
+    pub guardian_state: Option<Account<'info, GuardianState>>,
+
+    /// Treasury for the action's mint (payouts, deposit limits)
+    #[account(mut)]
+    pub treasury_state: Option<Account<'info, TreasuryState>>,
+
+    #[account(mut)]
+    pub treasury_ata: Option<Account<'info, TokenAccount>>,
+
+    /// Payout destination, or the user's deposit ATA for refunds
+    #[account(mut)]
+    pub destination: Option<Account<'info, TokenAccount>>,
+
+    /// User receiving a refund
+    #[account(mut)]
+    pub user_pda: Option<Account<'info, UserPDA>>,
+
+    /// Allowlist entry for the payout destination's owner
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// `SpendingLimit` PDA of the treasury (payouts) or of the key being limited
+    /// CHECK: Address and contents checked when the action runs
+    #[account(mut)]
+    pub spending_limit: Option<UncheckedAccount<'info>>,
+
+    /// Creates the `SpendingLimit` account when one is set for a new key
+    pub system_program: Option<Program<'info, System>>,
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Close a sale once its window has ended (only admin can do this)
+/// The sale succeeds if the soft cap was raised; otherwise it fails and
+/// buyers can reclaim their payment with `refund_sale_purchase`.
+pub fn finalize_sale(ctx: Context<FinalizeSale>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let sale = &mut ctx.accounts.sale;
+
+    require!(sale.status == SaleStatus::Active, ErrorCode::SaleAlreadyFinalized);
+    require!(now >= sale.end_time, ErrorCode::SaleNotEnded);
+
+    sale.status = if sale.raised >= sale.soft_cap {
+        SaleStatus::Succeeded
+    } else {
+        SaleStatus::Failed
+    };
+
+    msg!("✅ Sale {} finalized: {:?} (raised {}, sold {})", sale.key(), sale.status, sale.raised, sale.sold);
+
+    emit!(SaleFinalized {
+        sale: sale.key(),
+        succeeded: sale.status == SaleStatus::Succeeded,
+        raised: sale.raised,
+        sold: sale.sold,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSale<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"sale", sale.mint.as_ref()],
+        bump = sale.bump,
+    )]
+    pub sale: Account<'info, Sale>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
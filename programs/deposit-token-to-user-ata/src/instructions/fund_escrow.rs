@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::pda_transfer;
+
+/// Move funds from a buyer's deposit account into a new escrow held for a seller
+/// (only the buyer's deposit account owner can do this). Locked funds cannot be escrowed.
+pub fn fund_escrow(
+    ctx: Context<FundEscrow>,
+    escrow_id: u64,
+    buyer_id: String,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        ctx.accounts.buyer_pda.key() != ctx.accounts.seller_pda.key(),
+        ErrorCode::InvalidEscrowParties
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let available = ctx.accounts.buyer_pda.available_balance(
+        ctx.accounts.lock.as_deref(),
+        ctx.accounts.buyer_pda_ata.amount,
+        now,
+    )?;
+    require!(ctx.accounts.buyer_pda_ata.amount >= amount, ErrorCode::InsufficientBalance);
+    require!(amount <= available, ErrorCode::FundsLocked);
+
+    // Move the funds into the escrow ATA, signed by the buyer PDA
+    let buyer_id_bytes = buyer_id.as_bytes();
+    let seeds = &[
+        b"deposit",
+        buyer_id_bytes,
+        &[ctx.accounts.buyer_pda.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.buyer_pda_ata.to_account_info(),
+        ctx.accounts.escrow_ata.to_account_info(),
+        ctx.accounts.buyer_pda.to_account_info(),
+        signer_seeds,
+        amount,
+    )?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.escrow_id = escrow_id;
+    escrow.buyer_pda = ctx.accounts.buyer_pda.key();
+    escrow.seller_pda = ctx.accounts.seller_pda.key();
+    escrow.mint = ctx.accounts.mint.key();
+    escrow.amount = amount;
+    escrow.status = EscrowStatus::Funded;
+    escrow.created_at = now;
+    escrow.settled_at = 0;
+    escrow.bump = ctx.bumps.escrow;
+
+    msg!("✅ Escrow {} funded with {} tokens by user '{}'", escrow_id, amount, buyer_id);
+
+    emit!(EscrowFunded {
+        escrow_id,
+        escrow: escrow.key(),
+        buyer_pda: escrow.buyer_pda,
+        seller_pda: escrow.seller_pda,
+        mint: escrow.mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64, buyer_id: String)]
+pub struct FundEscrow<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"deposit", buyer_id.as_bytes()],
+        bump = buyer_pda.bump,
+        constraint = buyer_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = !buyer_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub buyer_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = buyer_pda,
+    )]
+    pub buyer_pda_ata: Account<'info, TokenAccount>,
+
+    /// Buyer's lock, required when `buyer_pda.has_lock`
+    #[account(
+        seeds = [b"lock", buyer_pda.key().as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+
+    /// Seller's deposit account, paid on release
+    #[account(
+        constraint = !seller_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub seller_pda: Account<'info, UserPDA>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Escrow::SPACE,
+        seeds = [b"escrow", buyer_pda.key().as_ref(), seller_pda.key().as_ref(), escrow_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Escrow's own ATA (owned by the escrow PDA)
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+    )]
+    pub escrow_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
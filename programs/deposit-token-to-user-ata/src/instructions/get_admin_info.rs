@@ -7,6 +7,8 @@ pub fn get_admin_info(ctx: Context<GetAdminInfo>) -> Result<AdminInfo> {
     let info = AdminInfo {
         admin: admin_state.admin,
         created_at: admin_state.created_at,
+        multisig_enabled: admin_state.multisig_enabled,
+        timelock_delay: admin_state.timelock_delay,
     };
 
     msg!("Admin Info - Current Admin: {}, Created At: {}",
@@ -28,4 +30,6 @@ pub struct GetAdminInfo<'info> {
 pub struct AdminInfo {
     pub admin: Pubkey,
     pub created_at: i64,
+    pub multisig_enabled: bool,
+    pub timelock_delay: i64,
 }
\ No newline at end of file
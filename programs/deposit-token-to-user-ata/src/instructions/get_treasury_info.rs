@@ -13,6 +13,9 @@ pub fn get_treasury_info(ctx: Context<GetTreasuryInfo>) -> Result<TreasuryInfo>
         treasury_ata: treasury_state.treasury_ata,
         balance: treasury_balance,
         created_at: treasury_state.created_at,
+        min_deposit: treasury_state.min_deposit,
+        max_deposit: treasury_state.max_deposit,
+        total_refunded: treasury_state.total_refunded,
     };
 
     msg!("Treasury Info - Mint: {}, ATA: {}, Balance: {}",
@@ -43,4 +46,7 @@ pub struct TreasuryInfo {
     pub treasury_ata: Pubkey,
     pub balance: u64, // Current balance of the treasury ATA
     pub created_at: i64,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+    pub total_refunded: u64,
 }
\ No newline at end of file
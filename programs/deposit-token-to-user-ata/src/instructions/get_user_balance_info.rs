@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::errors::ErrorCode;
+
+
+/// Get a user's locked and available balances (view function)
+pub fn get_user_balance_info(ctx: Context<GetUserBalanceInfo>) -> Result<UserBalanceInfo> {
+    let user_pda = &ctx.accounts.user_pda;
+    let lock = ctx.accounts.lock.as_deref();
+    let now = Clock::get()?.unix_timestamp;
+
+    let total = ctx.accounts.user_pda_ata.amount;
+    let available = user_pda.available_balance(lock, total, now)?;
+
+    let info = UserBalanceInfo {
+        user_id: user_pda.user_id.clone(),
+        total,
+        locked: total - available,
+        available,
+        unlock_at: lock.map_or(0, |lock| lock.unlock_at),
+        vesting_start: lock.map_or(0, |lock| lock.vesting_start),
+    };
+
+    msg!("Balance Info - User ID: {}, Total: {}, Locked: {}, Available: {}",
+             info.user_id, info.total, info.locked, info.available);
+
+    Ok(info)
+}
+
+
+#[derive(Accounts)]
+pub struct GetUserBalanceInfo<'info> {
+    #[account(
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"lock", user_pda.key().as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+}
+
+// Return type for get_user_balance_info
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserBalanceInfo {
+    pub user_id: String,
+    pub total: u64,
+    pub locked: u64,
+    pub available: u64,
+    pub unlock_at: i64,
+    pub vesting_start: i64,
+}
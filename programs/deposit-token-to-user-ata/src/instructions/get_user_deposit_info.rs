@@ -12,6 +12,14 @@ pub fn get_user_deposit_info(ctx: Context<GetUserDepositInfo>) -> Result<UserDep
         owner: user_pda.owner,
         token_account: user_pda.token_account,
         created_at: user_pda.created_at,
+        tier: user_pda.tier,
+        window_start: user_pda.window_start,
+        window_deposited: user_pda.window_deposited,
+        total_refunded: user_pda.total_refunded,
+        referrer: user_pda.referrer,
+        referred_volume: user_pda.referred_volume,
+        referral_rewards: user_pda.referral_rewards,
+        frozen: user_pda.frozen,
     };
 
     msg!("PDA Info - Address: {}, User ID: {}, ATA: {}",
@@ -34,4 +42,12 @@ pub struct UserDepositInfo {
     pub owner: Pubkey,
     pub token_account: Pubkey,
     pub created_at: i64,
+    pub tier: u8,
+    pub window_start: i64,
+    pub window_deposited: u64,
+    pub total_refunded: u64,
+    pub referrer: Option<Pubkey>,
+    pub referred_volume: u64,
+    pub referral_rewards: u64,
+    pub frozen: bool,
 }
@@ -14,6 +14,10 @@ pub fn initialize(ctx: Context<Initialize>, token_mint: Pubkey) -> Result<()> {
     admin_state.admin = ctx.accounts.payer.key();
     admin_state.bump = ctx.bumps.admin_state;
     admin_state.created_at = Clock::get()?.unix_timestamp;
+    admin_state.multisig_enabled = false;
+    admin_state.timelock_delay = 0;
+    admin_state.timelock_count = 0;
+    admin_state.version = ACCOUNT_VERSION;
 
     // Initialize treasury state
     let treasury_state = &mut ctx.accounts.treasury_state;
@@ -21,6 +25,7 @@ pub fn initialize(ctx: Context<Initialize>, token_mint: Pubkey) -> Result<()> {
     treasury_state.treasury_ata = ctx.accounts.treasury_ata.key();
     treasury_state.bump = ctx.bumps.treasury_state;
     treasury_state.created_at = Clock::get()?.unix_timestamp;
+    treasury_state.version = ACCOUNT_VERSION;
 
     msg!("✅ Program initialized successfully");
     msg!("🔑 Admin wallet: {}", admin_state.admin);
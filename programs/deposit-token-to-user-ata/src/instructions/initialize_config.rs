@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+
+/// Create the program config (only admin can do this)
+/// All limits and fees start disabled until the admin configures them.
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.tier_limits = [TierLimits::default(); MAX_TIERS];
+    program_config.fee_bps = 0;
+    program_config.fee_recipient = Pubkey::default();
+    program_config.referral_bps = 0;
+    program_config.paused = false;
+    program_config.destination_delay = DEFAULT_DESTINATION_DELAY;
+    program_config.attester = Pubkey::default();
+    program_config.kyc_threshold = 0;
+    program_config.kyc_min_level = 0;
+    program_config.voucher_signer = Pubkey::default();
+    program_config.bump = ctx.bumps.program_config;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Program config initialized: {}", program_config.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        create_metadata_accounts_v3,
+        mpl_token_metadata::types::DataV2,
+        CreateMetadataAccountsV3,
+        Metadata,
+    },
+    token::{Token, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Create a new token with Metaplex metadata and open a sale for it (only admin can do this)
+/// The sale PDA becomes the mint authority, so tokens only come into existence
+/// through `buy`. Buyers pay in `payment_mint`, which must have a treasury.
+#[allow(clippy::too_many_arguments)]
+pub fn initiate_token(
+    ctx: Context<InitToken>,
+    metadata: InitTokenParams,
+    sale_start_time: i64,
+    sale_end_time: i64,
+    price: u64,
+    soft_cap: u64,
+    hard_cap: u64,
+) -> Result<()> {
+    require!(sale_start_time < sale_end_time, ErrorCode::InvalidSaleWindow);
+    require!(sale_end_time > Clock::get()?.unix_timestamp, ErrorCode::InvalidSaleWindow);
+    require!(price > 0, ErrorCode::InvalidAmount);
+    require!(hard_cap > 0, ErrorCode::InvalidAmount);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        b"sale",
+        mint_key.as_ref(),
+        &[ctx.bumps.sale],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Attach Metaplex metadata, signed by the sale PDA as mint authority
+    let cpi_accounts = CreateMetadataAccountsV3 {
+        metadata: ctx.accounts.metadata.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        mint_authority: ctx.accounts.sale.to_account_info(),
+        payer: ctx.accounts.admin.to_account_info(),
+        update_authority: ctx.accounts.admin.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_metadata_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+    let token_data = DataV2 {
+        name: metadata.name.clone(),
+        symbol: metadata.symbol.clone(),
+        uri: metadata.uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    create_metadata_accounts_v3(cpi_ctx, token_data, true, true, None)?;
+
+    let sale = &mut ctx.accounts.sale;
+    sale.mint = mint_key;
+    sale.payment_mint = ctx.accounts.payment_mint.key();
+    sale.price = price;
+    sale.start_time = sale_start_time;
+    sale.end_time = sale_end_time;
+    sale.soft_cap = soft_cap;
+    sale.hard_cap = hard_cap;
+    sale.sold = 0;
+    sale.raised = 0;
+    sale.status = SaleStatus::Active;
+    sale.created_at = Clock::get()?.unix_timestamp;
+    sale.bump = ctx.bumps.sale;
+
+    msg!("✅ Created token {} ({}) with sale {}", metadata.name, metadata.symbol, sale.key());
+    msg!("🕒 Sale window: {} - {}", sale_start_time, sale_end_time);
+
+    emit!(SaleCreated {
+        sale: sale.key(),
+        mint: mint_key,
+        payment_mint: sale.payment_mint,
+        price,
+        start_time: sale_start_time,
+        end_time: sale_end_time,
+        soft_cap,
+        hard_cap,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: InitTokenParams)]
+pub struct InitToken<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Sale::SPACE,
+        seeds = [b"sale", mint.key().as_ref()],
+        bump
+    )]
+    pub sale: Box<Account<'info, Sale>>,
+
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = params.decimals,
+        mint::authority = sale,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Metaplex metadata PDA, created and validated by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Token buyers pay with; must already have a treasury
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"treasury", payment_mint.key().as_ref()],
+        bump = treasury_state.bump,
+    )]
+    pub treasury_state: Box<Account<'info, TreasuryState>>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Token metadata for initiate_token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitTokenParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub decimals: u8,
+}
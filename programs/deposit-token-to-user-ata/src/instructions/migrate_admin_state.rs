@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::grow_account;
+
+/// Bring the `AdminState` account up to the current layout (only admin can do this)
+/// Takes the raw account so layouts the current struct cannot read are
+/// handled too: it is grown to `AdminState::SPACE`, new fields are zeroed
+/// and `version` is set. Already current accounts are left untouched.
+/// Signed by the admin key even with the multisig or timelock on, as no
+/// setting changes; the admin pays the rent for the extra space.
+pub fn migrate_admin_state(ctx: Context<MigrateAdminState>) -> Result<()> {
+    let admin_state = ctx.accounts.admin_state.to_account_info();
+    require_keys_eq!(*admin_state.owner, crate::ID, ErrorCode::InvalidMigrationAccount);
+    {
+        // Every layout starts with the discriminator and the admin key
+        let data = admin_state.try_borrow_data()?;
+        require!(
+            data.len() >= 40 && data[..8] == AdminState::DISCRIMINATOR,
+            ErrorCode::InvalidMigrationAccount
+        );
+        require!(
+            data[8..40] == ctx.accounts.admin.key().to_bytes(),
+            ErrorCode::UnauthorizedAdmin
+        );
+    }
+
+    grow_account(
+        &admin_state,
+        AdminState::SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut data = admin_state.try_borrow_mut_data()?;
+    let mut state = AdminState::try_deserialize(&mut &data[..])?;
+    let from_version = state.version;
+    if from_version >= ACCOUNT_VERSION {
+        msg!("AdminState already at version {}", from_version);
+        return Ok(());
+    }
+
+    state.version = ACCOUNT_VERSION;
+    state.try_serialize(&mut &mut data[..])?;
+
+    msg!("🔧 AdminState migrated from version {} to {}", from_version, ACCOUNT_VERSION);
+
+    emit!(AccountMigrated {
+        account: admin_state.key(),
+        from_version,
+        to_version: ACCOUNT_VERSION,
+        size: data.len() as u64,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateAdminState<'info> {
+    /// CHECK: Address checked by seeds; owner, discriminator and admin key checked in the handler
+    #[account(
+        mut,
+        seeds = [b"admin"],
+        bump,
+    )]
+    pub admin_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
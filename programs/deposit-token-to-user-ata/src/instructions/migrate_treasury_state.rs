@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::grow_account;
+
+/// Bring the `TreasuryState` of `token_mint` up to the current layout (only admin can do this)
+/// Treasuries created before versioning are too short to be read as a
+/// `TreasuryState`; they are grown to `TreasuryState::SPACE`, the new fields
+/// start zeroed and `version` is set. Already current accounts are left untouched.
+/// Signed by the admin key even with the multisig or timelock on, as no
+/// setting changes; the admin pays the rent for the extra space.
+pub fn migrate_treasury_state(ctx: Context<MigrateTreasuryState>, token_mint: Pubkey) -> Result<()> {
+    let treasury_state = ctx.accounts.treasury_state.to_account_info();
+    require_keys_eq!(*treasury_state.owner, crate::ID, ErrorCode::InvalidMigrationAccount);
+    {
+        let data = treasury_state.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == TreasuryState::DISCRIMINATOR,
+            ErrorCode::InvalidMigrationAccount
+        );
+    }
+
+    grow_account(
+        &treasury_state,
+        TreasuryState::SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut data = treasury_state.try_borrow_mut_data()?;
+    let mut state = TreasuryState::try_deserialize(&mut &data[..])?;
+    let from_version = state.version;
+    if from_version >= ACCOUNT_VERSION {
+        msg!("TreasuryState for mint {} already at version {}", token_mint, from_version);
+        return Ok(());
+    }
+
+    state.version = ACCOUNT_VERSION;
+    state.try_serialize(&mut &mut data[..])?;
+
+    msg!("🔧 TreasuryState for mint {} migrated from version {} to {}", token_mint, from_version, ACCOUNT_VERSION);
+
+    emit!(AccountMigrated {
+        account: treasury_state.key(),
+        from_version,
+        to_version: ACCOUNT_VERSION,
+        size: data.len() as u64,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(token_mint: Pubkey)]
+pub struct MigrateTreasuryState<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    /// CHECK: Address checked by seeds; owner and discriminator checked in the handler
+    #[account(
+        mut,
+        seeds = [b"treasury", token_mint.as_ref()],
+        bump,
+    )]
+    pub treasury_state: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
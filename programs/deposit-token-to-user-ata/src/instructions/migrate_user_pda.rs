@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::grow_account;
+
+/// Bring a `UserPDA` up to the current layout (only admin can do this)
+/// Accounts created before later fields were added are too short to be read
+/// as a `UserPDA`; they are grown to `UserPDA::space`, the new fields start
+/// zeroed (their defaults) and `version` is set. Already current accounts are
+/// left untouched. Signed by the admin key even with the multisig or timelock
+/// on, as no setting changes; the admin pays the rent for the extra space.
+pub fn migrate_user_pda(ctx: Context<MigrateUserPda>, user_id: String) -> Result<()> {
+    migrate_user_pda_account(
+        &ctx.accounts.user_pda.to_account_info(),
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!("🔧 User '{}' is at version {}", user_id, ACCOUNT_VERSION);
+
+    Ok(())
+}
+
+/// Migrate one `UserPDA` account; shared with `migrate_user_pdas`
+pub fn migrate_user_pda_account<'info>(
+    user_pda: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require_keys_eq!(*user_pda.owner, crate::ID, ErrorCode::InvalidMigrationAccount);
+    let id_len = {
+        let data = user_pda.try_borrow_data()?;
+        require!(
+            data.len() >= 12 && data[..8] == UserPDA::DISCRIMINATOR,
+            ErrorCode::InvalidMigrationAccount
+        );
+        let mut len_bytes = [0u8; 4];
+        len_bytes.copy_from_slice(&data[8..12]);
+        u32::from_le_bytes(len_bytes) as usize
+    };
+
+    grow_account(user_pda, UserPDA::space_for_id_len(id_len), payer, system_program)?;
+
+    let mut data = user_pda.try_borrow_mut_data()?;
+    let mut account = UserPDA::try_deserialize(&mut &data[..])?;
+    let from_version = account.version;
+    if from_version >= ACCOUNT_VERSION {
+        return Ok(());
+    }
+
+    account.version = ACCOUNT_VERSION;
+    account.try_serialize(&mut &mut data[..])?;
+
+    emit!(AccountMigrated {
+        account: user_pda.key(),
+        from_version,
+        to_version: ACCOUNT_VERSION,
+        size: data.len() as u64,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct MigrateUserPda<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    /// CHECK: Address checked by seeds; owner and discriminator checked in the handler
+    #[account(
+        mut,
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump,
+    )]
+    pub user_pda: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::migrate_user_pda::migrate_user_pda_account;
+
+/// Maximum number of accounts migrated in one transaction
+pub const MAX_MIGRATION_BATCH: usize = 10;
+
+/// Batch variant of `migrate_user_pda` (only admin can do this)
+/// The `UserPDA` accounts to migrate are passed, writable, in `remaining_accounts`.
+/// Like the single variant it takes the admin key directly, and the admin pays
+/// the rent for every account that grows.
+pub fn migrate_user_pdas<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateUserPdas<'info>>,
+) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), ErrorCode::EmptyUserList);
+    require!(ctx.remaining_accounts.len() <= MAX_MIGRATION_BATCH, ErrorCode::TooManyUsers);
+
+    let payer = ctx.accounts.admin.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    for user_pda in ctx.remaining_accounts {
+        migrate_user_pda_account(user_pda, &payer, &system_program)?;
+    }
+
+    msg!("🔧 {} user accounts at version {}", ctx.remaining_accounts.len(), ACCOUNT_VERSION);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserPdas<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // `remaining_accounts`: the UserPDA accounts to migrate
+}
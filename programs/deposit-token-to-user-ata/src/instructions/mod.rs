@@ -18,6 +18,9 @@ pub use find_user_pda_address::*;
 pub mod deposit_tokens;
 pub use deposit_tokens::*;
 
+pub mod close_deposit_receipt;
+pub use close_deposit_receipt::*;
+
 pub mod get_admin_info;
 pub use get_admin_info::*;
 
@@ -31,4 +34,166 @@ pub mod admin_transfer_to_treasury;
 pub use admin_transfer_to_treasury::*;
 
 pub mod update_admin;
-pub use update_admin::*;
\ No newline at end of file
+pub use update_admin::*;
+
+pub mod update_deposit_limits;
+pub use update_deposit_limits::*;
+
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod update_tier_limits;
+pub use update_tier_limits::*;
+
+pub mod set_user_tier;
+pub use set_user_tier::*;
+
+pub mod migrate_user_pda;
+pub use migrate_user_pda::*;
+
+pub mod update_fee_config;
+pub use update_fee_config::*;
+
+pub mod update_sweep_splits;
+pub use update_sweep_splits::*;
+
+pub mod create_plan;
+pub use create_plan::*;
+
+pub mod create_subscription;
+pub use create_subscription::*;
+
+pub mod charge_subscription;
+pub use charge_subscription::*;
+
+pub mod change_plan;
+pub use change_plan::*;
+
+pub mod cancel_subscription;
+pub use cancel_subscription::*;
+
+pub mod initiate_token;
+pub use initiate_token::*;
+
+pub mod buy;
+pub use buy::*;
+
+pub mod finalize_sale;
+pub use finalize_sale::*;
+
+pub mod refund_sale_purchase;
+pub use refund_sale_purchase::*;
+
+pub mod get_user_balance_info;
+pub use get_user_balance_info::*;
+
+pub mod fund_escrow;
+pub use fund_escrow::*;
+
+pub mod release_escrow;
+pub use release_escrow::*;
+
+pub mod refund_escrow;
+pub use refund_escrow::*;
+
+pub mod resolve_escrow_dispute;
+pub use resolve_escrow_dispute::*;
+
+pub mod transfer_between_users;
+pub use transfer_between_users::*;
+
+pub mod refund_to_user;
+pub use refund_to_user::*;
+
+pub mod redeem_voucher;
+pub use redeem_voucher::*;
+
+pub mod update_voucher_signer;
+pub use update_voucher_signer::*;
+
+pub mod create_distribution;
+pub use create_distribution::*;
+
+pub mod claim;
+pub use claim::*;
+
+pub mod clawback;
+pub use clawback::*;
+
+pub mod update_referral_config;
+pub use update_referral_config::*;
+
+pub mod claim_referral_rewards;
+pub use claim_referral_rewards::*;
+
+pub mod configure_admin_set;
+pub use configure_admin_set::*;
+
+pub mod propose_action;
+pub use propose_action::*;
+
+pub mod approve_action;
+pub use approve_action::*;
+
+pub mod execute_action;
+pub use execute_action::*;
+
+pub mod enable_timelock;
+pub use enable_timelock::*;
+
+pub mod queue_action;
+pub use queue_action::*;
+
+pub mod cancel_queued_action;
+pub use cancel_queued_action::*;
+
+pub mod execute_queued_action;
+pub use execute_queued_action::*;
+
+pub mod set_guardian;
+pub use set_guardian::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+pub mod set_user_frozen;
+pub use set_user_frozen::*;
+
+pub mod veto_proposal;
+pub use veto_proposal::*;
+
+pub mod set_spending_limit;
+pub use set_spending_limit::*;
+
+pub mod add_allowed_destination;
+pub use add_allowed_destination::*;
+
+pub mod remove_allowed_destination;
+pub use remove_allowed_destination::*;
+
+pub mod update_destination_delay;
+pub use update_destination_delay::*;
+
+pub mod block_wallet;
+pub use block_wallet::*;
+
+pub mod unblock_wallet;
+pub use unblock_wallet::*;
+
+pub mod update_kyc_config;
+pub use update_kyc_config::*;
+
+pub mod attest_user;
+pub use attest_user::*;
+
+pub mod revoke_attestation;
+pub use revoke_attestation::*;
+
+pub mod migrate_admin_state;
+pub use migrate_admin_state::*;
+
+pub mod migrate_user_pdas;
+pub use migrate_user_pdas::*;
+
+pub mod migrate_treasury_state;
+pub use migrate_treasury_state::*;
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Propose a privileged action (only admin set members can do this)
+/// The proposer's approval is recorded right away.
+pub fn propose_action(ctx: Context<ProposeAction>, action: AdminAction) -> Result<()> {
+    require!(action.fits(), ErrorCode::InvalidAdminAction);
+    // The timelock delay can only be changed through the timelock itself
+    require!(
+        !matches!(action, AdminAction::UpdateTimelockDelay { .. }),
+        ErrorCode::InvalidAdminAction
+    );
+
+    let admin_set = &mut ctx.accounts.admin_set;
+    let index = admin_set.member_index(&ctx.accounts.proposer.key())?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.proposal_id = admin_set.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.action = action.clone();
+    proposal.set_version = admin_set.version;
+    proposal.approvals = 0;
+    proposal.approval_count = 0;
+    proposal.created_at = now;
+    proposal.expires_at = now + PROPOSAL_LIFETIME;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.approve(index)?;
+
+    admin_set.proposal_count += 1;
+
+    msg!("📝 Proposal {} created by {}", proposal.proposal_id, proposal.proposer);
+
+    emit!(ActionProposed {
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        action,
+        expires_at: proposal.expires_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = admin_state.multisig_enabled @ ErrorCode::MultisigNotEnabled,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", admin_set.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Queue an admin action behind the timelock (only admin can do this)
+/// It can be executed once `eta` (now + the timelock delay) has passed.
+pub fn queue_action(ctx: Context<QueueAction>, action: AdminAction) -> Result<()> {
+    require!(action.fits(), ErrorCode::InvalidAdminAction);
+    // The multisig and the timelock are exclusive; hand over to an admin set
+    // by first removing the timelock
+    require!(
+        !matches!(action, AdminAction::UpdateAdminSet { .. }),
+        ErrorCode::InvalidAdminAction
+    );
+
+    let admin_state = &mut ctx.accounts.admin_state;
+    let now = Clock::get()?.unix_timestamp;
+
+    let queued_action = &mut ctx.accounts.queued_action;
+    queued_action.action_id = admin_state.timelock_count;
+    queued_action.action = action.clone();
+    queued_action.queued_by = ctx.accounts.admin.key();
+    queued_action.eta = now + admin_state.timelock_delay;
+    queued_action.created_at = now;
+    queued_action.bump = ctx.bumps.queued_action;
+
+    admin_state.timelock_count += 1;
+
+    msg!("⏳ Action {} queued, executable after {}", queued_action.action_id, queued_action.eta);
+
+    emit!(ActionQueued {
+        action_id: queued_action.action_id,
+        action,
+        eta: queued_action.eta,
+        queued_by: queued_action.queued_by,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct QueueAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = admin_state.timelock_delay > 0 @ ErrorCode::TimelockNotEnabled,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = QueuedAction::SPACE,
+        seeds = [b"queued_action", admin_state.timelock_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{pda_transfer, verify_ed25519_ix};
+
+/// Withdraw from a user deposit account with a signed voucher
+/// (only the deposit account owner can submit it).
+///
+/// The instruction right before this one must be an Ed25519 program
+/// instruction over the Borsh-serialized `Voucher`, signed by the configured
+/// voucher signer.
+/// Each nonce can be redeemed once per user and locked funds stay put.
+pub fn redeem_voucher(
+    ctx: Context<RedeemVoucher>,
+    user_id: String,
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= expiry, ErrorCode::VoucherExpired);
+    require!(ctx.accounts.redemption.redeemed_at == 0, ErrorCode::VoucherAlreadyRedeemed);
+
+    // The voucher signer must have signed exactly these voucher terms
+    let voucher_signer = ctx.accounts.program_config.voucher_signer;
+    require!(voucher_signer != Pubkey::default(), ErrorCode::VoucherSignerNotSet);
+    let voucher = Voucher {
+        program_id: crate::ID,
+        user_id: user_id.clone(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+        destination: ctx.accounts.destination.key(),
+        nonce,
+        expiry,
+    };
+    let message = voucher.try_to_vec()?;
+
+    let ix_sysvar = ctx.accounts.instructions_sysvar.to_account_info();
+    let current_index = load_current_index_checked(&ix_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidVoucherSignature);
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, &ix_sysvar)?;
+    verify_ed25519_ix(&ed25519_ix, &voucher_signer, &message)?;
+
+    // Large withdrawals need a KYC attestation
+    ctx.accounts.program_config.check_kyc(ctx.accounts.attestation.as_deref(), amount, now)?;
+
+    // Balance and lockups
+    require!(ctx.accounts.user_pda_ata.amount >= amount, ErrorCode::InsufficientBalance);
+    let available = ctx.accounts.user_pda.available_balance(
+        ctx.accounts.lock.as_deref(),
+        ctx.accounts.user_pda_ata.amount,
+        now,
+    )?;
+    require!(amount <= available, ErrorCode::FundsLocked);
+
+    let user_id_bytes = user_id.as_bytes();
+    let seeds = &[
+        b"deposit",
+        user_id_bytes,
+        &[ctx.accounts.user_pda.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.user_pda_ata.to_account_info(),
+        ctx.accounts.destination.to_account_info(),
+        ctx.accounts.user_pda.to_account_info(),
+        signer_seeds,
+        amount,
+    )?;
+
+    let redemption = &mut ctx.accounts.redemption;
+    redemption.user_pda = ctx.accounts.user_pda.key();
+    redemption.destination = ctx.accounts.destination.key();
+    redemption.amount = amount;
+    redemption.nonce = nonce;
+    redemption.redeemed_at = now;
+    redemption.bump = ctx.bumps.redemption;
+
+    msg!("🎟️ Redeemed voucher {} for user '{}': {} tokens", nonce, user_id, amount);
+
+    emit!(VoucherRedeemed {
+        user_id,
+        amount,
+        destination: redemption.destination,
+        nonce,
+        expiry,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String, amount: u64, nonce: u64)]
+pub struct RedeemVoucher<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    /// User's lock, required when `user_pda.has_lock`
+    #[account(
+        seeds = [b"lock", user_pda.key().as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+
+    /// User's KYC attestation, required above `ProgramConfig::kyc_threshold`
+    #[account(
+        seeds = [b"attestation", user_pda.key().as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    /// Token account named in the voucher
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// Replay protection for the voucher nonce
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = VoucherRedemption::SPACE,
+        seeds = [b"voucher", user_pda.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption: Account<'info, VoucherRedemption>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
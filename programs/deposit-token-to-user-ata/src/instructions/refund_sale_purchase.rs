@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{charge_spending_limit, pda_transfer};
+
+/// Unwind a purchase from a failed sale (only the deposit account owner can do this)
+/// The bought tokens are burned from the user PDA's sale ATA and the payment is
+/// returned from the treasury into the user's deposit ATA, counting against the
+/// treasury's spending limit.
+pub fn refund_sale_purchase(
+    ctx: Context<RefundSalePurchase>,
+    user_id: String,
+) -> Result<()> {
+    require!(ctx.accounts.sale.status == SaleStatus::Failed, ErrorCode::SaleNotFailed);
+
+    let tokens = ctx.accounts.purchase.tokens;
+    let paid = ctx.accounts.purchase.paid;
+
+    // Burn the sale tokens, signed by the user PDA
+    let user_id_bytes = user_id.as_bytes();
+    let seeds = &[
+        b"deposit",
+        user_id_bytes,
+        &[ctx.accounts.user_pda.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = anchor_spl::token::Burn {
+        mint: ctx.accounts.sale_mint.to_account_info(),
+        from: ctx.accounts.user_sale_ata.to_account_info(),
+        authority: ctx.accounts.user_pda.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    anchor_spl::token::burn(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds), tokens)?;
+
+    // Return the payment from the treasury
+    charge_spending_limit(&ctx.accounts.spending_limit, paid, Clock::get()?.unix_timestamp)?;
+
+    let payment_mint_key = ctx.accounts.payment_mint.key();
+    let seeds = &[
+        b"treasury",
+        payment_mint_key.as_ref(),
+        &[ctx.accounts.treasury_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        ctx.accounts.user_pda_ata.to_account_info(),
+        ctx.accounts.treasury_state.to_account_info(),
+        signer_seeds,
+        paid,
+    )?;
+
+    msg!("✅ Refunded {} to user '{}' for failed sale, burned {} tokens", paid, user_id, tokens);
+
+    emit!(SaleRefunded {
+        user_id,
+        sale: ctx.accounts.sale.key(),
+        tokens_burned: tokens,
+        refunded: paid,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct RefundSalePurchase<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"sale", sale_mint.key().as_ref()],
+        bump = sale.bump,
+        has_one = payment_mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub sale: Box<Account<'info, Sale>>,
+
+    #[account(
+        mut,
+        address = sale.mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub sale_mint: Box<Account<'info, Mint>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"treasury", payment_mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Box<Account<'info, TreasuryState>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+        constraint = !user_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub user_pda: Box<Account<'info, UserPDA>>,
+
+    /// User's deposit ATA for the payment mint, receives the refund
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = sale_mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_sale_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"purchase", sale.key().as_ref(), user_pda.key().as_ref()],
+        bump = purchase.bump,
+    )]
+    pub purchase: Box<Account<'info, SalePurchase>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", treasury_state.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::{charge_spending_limit, pda_transfer};
+
+/// Reverse a swept payment by paying it back from the treasury into the user's
+/// deposit ATA (only admin can do this). `original_tx_ref` is the signature of
+/// the sweep being reversed and is carried in `RefundIssued` for reconciliation.
+/// Refunds count against the treasury's spending limit; with the multisig or
+/// timelock on they go through a `RefundToUser` action instead.
+pub fn refund_to_user(
+    ctx: Context<RefundToUser>,
+    user_id: String,
+    amount: u64,
+    reason_code: u16,
+    original_tx_ref: [u8; 64],
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        ctx.accounts.treasury_ata.amount >= amount,
+        ErrorCode::InsufficientBalance
+    );
+    charge_spending_limit(&ctx.accounts.spending_limit, amount, Clock::get()?.unix_timestamp)?;
+
+    // Treasury PDA signs for its ATA
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        b"treasury",
+        mint_key.as_ref(),
+        &[ctx.accounts.treasury_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        ctx.accounts.user_pda_ata.to_account_info(),
+        ctx.accounts.treasury_state.to_account_info(),
+        signer_seeds,
+        amount,
+    )?;
+
+    let user_pda = &mut ctx.accounts.user_pda;
+    user_pda.total_refunded = user_pda
+        .total_refunded
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    treasury_state.total_refunded = treasury_state
+        .total_refunded
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("↩️ Refunded {} tokens from treasury to user '{}' (reason {})", amount, user_id, reason_code);
+
+    emit!(RefundIssued {
+        user_id,
+        amount,
+        reason_code,
+        original_tx_ref,
+        admin: ctx.accounts.admin.key(),
+        to_ata: ctx.accounts.user_pda_ata.key(),
+        user_total_refunded: user_pda.total_refunded,
+        treasury_total_refunded: treasury_state.total_refunded,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct RefundToUser<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.treasury_ata == treasury_ata.key() @ ErrorCode::InvalidTreasuryATA,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_state,
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+        constraint = user_pda.token_account == user_pda_ata.key() @ ErrorCode::InvalidUserATA,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user_pda,
+    )]
+    pub user_pda_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Address checked by seeds, contents by `charge_spending_limit`
+    #[account(
+        mut,
+        seeds = [b"spending_limit", treasury_state.key().as_ref()],
+        bump,
+    )]
+    pub spending_limit: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
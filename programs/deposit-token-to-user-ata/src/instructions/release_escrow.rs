@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::settle_escrow;
+
+/// Pay an escrow out to the seller's deposit account
+/// (only the buyer's deposit account owner can do this)
+pub fn release_escrow(ctx: Context<ReleaseEscrow>, escrow_id: u64) -> Result<()> {
+    require!(ctx.accounts.escrow.status == EscrowStatus::Funded, ErrorCode::EscrowAlreadySettled);
+
+    settle_escrow(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.escrow,
+        ctx.accounts.escrow_ata.to_account_info(),
+        ctx.accounts.seller_pda_ata.to_account_info(),
+    )?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.status = EscrowStatus::Released;
+    escrow.settled_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Escrow {} released {} tokens to the seller", escrow_id, escrow.amount);
+
+    emit!(EscrowReleased {
+        escrow_id,
+        escrow: escrow.key(),
+        seller_pda: escrow.seller_pda,
+        amount: escrow.amount,
+        authority: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct ReleaseEscrow<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.buyer_pda.as_ref(), escrow.seller_pda.as_ref(), escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+    )]
+    pub escrow_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        address = escrow.buyer_pda @ ErrorCode::InvalidPDA,
+        constraint = buyer_pda.owner == owner.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = !buyer_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub buyer_pda: Account<'info, UserPDA>,
+
+    #[account(
+        address = escrow.seller_pda @ ErrorCode::InvalidPDA,
+        constraint = !seller_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub seller_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller_pda,
+    )]
+    pub seller_pda_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Remove a payout destination from the allowlist (admin or guardian)
+/// The rent goes back to the admin who added it.
+pub fn remove_allowed_destination(
+    ctx: Context<RemoveAllowedDestination>,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let is_guardian = ctx
+        .accounts
+        .guardian_state
+        .as_ref()
+        .is_some_and(|guardian_state| guardian_state.guardian == authority);
+    require!(
+        authority == ctx.accounts.admin_state.admin || is_guardian,
+        ErrorCode::UnauthorizedAdmin
+    );
+
+    msg!("🚫 Payout destination {} removed", owner);
+
+    emit!(AllowedDestinationRemoved {
+        mint,
+        owner,
+        removed_by: authority,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, owner: Pubkey)]
+pub struct RemoveAllowedDestination<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    /// Required when the guardian is removing
+    #[account(
+        seeds = [b"guardian"],
+        bump = guardian_state.bump,
+    )]
+    pub guardian_state: Option<Account<'info, GuardianState>>,
+
+    #[account(
+        mut,
+        close = added_by,
+        seeds = [b"allowed_destination", mint.as_ref(), owner.as_ref()],
+        bump = allowed_destination.bump,
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    /// CHECK: Receives the allowlist entry rent, checked by address
+    #[account(
+        mut,
+        address = allowed_destination.added_by,
+    )]
+    pub added_by: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::settle_escrow;
+
+/// Settle a disputed escrow either way (only admin can do this)
+pub fn resolve_escrow_dispute(
+    ctx: Context<ResolveEscrowDispute>,
+    escrow_id: u64,
+    release_to_seller: bool,
+) -> Result<()> {
+    require!(ctx.accounts.escrow.status == EscrowStatus::Funded, ErrorCode::EscrowAlreadySettled);
+
+    let destination = if release_to_seller {
+        ctx.accounts.seller_pda_ata.to_account_info()
+    } else {
+        ctx.accounts.buyer_pda_ata.to_account_info()
+    };
+    settle_escrow(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.escrow,
+        ctx.accounts.escrow_ata.to_account_info(),
+        destination,
+    )?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.status = if release_to_seller {
+        EscrowStatus::Released
+    } else {
+        EscrowStatus::Refunded
+    };
+    escrow.settled_at = Clock::get()?.unix_timestamp;
+
+    msg!("⚖️ Admin resolved escrow {}: {:?}", escrow_id, escrow.status);
+
+    emit!(EscrowDisputeResolved {
+        escrow_id,
+        escrow: escrow.key(),
+        release_to_seller,
+        amount: escrow.amount,
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct ResolveEscrowDispute<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.buyer_pda.as_ref(), escrow.seller_pda.as_ref(), escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = mint @ ErrorCode::InvalidTokenMint,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+    )]
+    pub escrow_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        address = escrow.buyer_pda @ ErrorCode::InvalidPDA,
+        constraint = !buyer_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub buyer_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = buyer_pda,
+    )]
+    pub buyer_pda_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        address = escrow.seller_pda @ ErrorCode::InvalidPDA,
+        constraint = !seller_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub seller_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller_pda,
+    )]
+    pub seller_pda_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
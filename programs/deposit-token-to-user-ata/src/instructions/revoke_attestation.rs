@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Revoke a user's KYC attestation (only admin can do this)
+/// The attester can issue a new one later with `attest_user`.
+pub fn revoke_attestation(ctx: Context<RevokeAttestation>, user_id: String) -> Result<()> {
+    ctx.accounts.attestation.revoked = true;
+
+    msg!("🚫 KYC attestation for user '{}' revoked", user_id);
+
+    emit!(AttestationRevoked {
+        user_pda: ctx.accounts.user_pda.key(),
+        revoked_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        seeds = [b"attestation", user_pda.key().as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
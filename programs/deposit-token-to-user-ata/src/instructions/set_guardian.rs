@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Set the guardian key (only admin can do this)
+pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let guardian_state = &mut ctx.accounts.guardian_state;
+    let old_guardian = guardian_state.set_guardian(guardian, Clock::get()?.unix_timestamp)?;
+    guardian_state.bump = ctx.bumps.guardian_state;
+
+    msg!("🛡️ Guardian set to {}", guardian);
+
+    emit!(GuardianUpdated {
+        old_guardian,
+        new_guardian: guardian,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = GuardianState::SPACE,
+        seeds = [b"guardian"],
+        bump
+    )]
+    pub guardian_state: Account<'info, GuardianState>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
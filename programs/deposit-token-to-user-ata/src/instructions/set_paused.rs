@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Pause or resume the program
+/// The guardian or the admin can pause; only the guardian can resume, so a
+/// pause made before a guardian is set holds until `set_guardian` names one.
+pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let guardian_state = ctx.accounts.guardian_state.as_deref();
+
+    if paused {
+        require!(
+            GuardianState::can_halt(guardian_state, &authority, &ctx.accounts.admin_state.admin),
+            ErrorCode::UnauthorizedGuardian
+        );
+    } else {
+        require!(
+            GuardianState::can_resume(guardian_state, &authority),
+            ErrorCode::UnauthorizedGuardian
+        );
+    }
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.paused = paused;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("{} Program {} by {}", if paused { "⏸️" } else { "▶️" }, if paused { "paused" } else { "resumed" }, authority);
+
+    emit!(ProgramPauseUpdated {
+        paused,
+        updated_by: authority,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    /// Required when the guardian is acting and for every resume
+    #[account(
+        seeds = [b"guardian"],
+        bump = guardian_state.bump,
+    )]
+    pub guardian_state: Option<Account<'info, GuardianState>>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Set the outflow caps for a key (only admin can do this)
+/// `key` is a sweeper wallet for `admin_transfer_to_treasury` /
+/// `admin_batch_transfer_to_treasury`, or a `TreasuryState` address for
+/// treasury outflows. A cap of 0 disables it. The first limit for a key can
+/// be anything; after that this only tightens the caps, raising or removing
+/// one needs a `SetSpendingLimit` proposal or queued action.
+pub fn set_spending_limit(
+    ctx: Context<SetSpendingLimit>,
+    key: Pubkey,
+    max_per_tx: u64,
+    max_per_window: u64,
+) -> Result<()> {
+    let spending_limit = &mut ctx.accounts.spending_limit;
+    let is_new = spending_limit.key == Pubkey::default();
+    require!(
+        is_new || spending_limit.tightens(max_per_tx, max_per_window),
+        ErrorCode::LimitIncreaseNotAllowed
+    );
+    spending_limit.set_caps(max_per_tx, max_per_window, Clock::get()?.unix_timestamp)?;
+    spending_limit.key = key;
+    spending_limit.bump = ctx.bumps.spending_limit;
+
+    msg!("✅ Spending limit for {} set to {} per tx / {} per window", key, max_per_tx, max_per_window);
+
+    emit!(SpendingLimitUpdated {
+        key,
+        max_per_tx,
+        max_per_window,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct SetSpendingLimit<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = SpendingLimit::SPACE,
+        seeds = [b"spending_limit", key.as_ref()],
+        bump
+    )]
+    pub spending_limit: Account<'info, SpendingLimit>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Freeze or unfreeze a user deposit account
+/// The guardian or the admin can freeze; only the guardian can unfreeze, so a
+/// freeze made before a guardian is set holds until `set_guardian` names one.
+pub fn set_user_frozen(ctx: Context<SetUserFrozen>, user_id: String, frozen: bool) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let guardian_state = ctx.accounts.guardian_state.as_deref();
+
+    if frozen {
+        require!(
+            GuardianState::can_halt(guardian_state, &authority, &ctx.accounts.admin_state.admin),
+            ErrorCode::UnauthorizedGuardian
+        );
+    } else {
+        require!(
+            GuardianState::can_resume(guardian_state, &authority),
+            ErrorCode::UnauthorizedGuardian
+        );
+    }
+
+    ctx.accounts.user_pda.frozen = frozen;
+
+    msg!("{} User {} {}", if frozen { "🧊" } else { "🔥" }, user_id, if frozen { "frozen" } else { "unfrozen" });
+
+    emit!(UserFreezeUpdated {
+        user_pda: ctx.accounts.user_pda.key(),
+        frozen,
+        updated_by: authority,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct SetUserFrozen<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    /// Required when the guardian is acting and for every resume
+    #[account(
+        seeds = [b"guardian"],
+        bump = guardian_state.bump,
+    )]
+    pub guardian_state: Option<Account<'info, GuardianState>>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Move a user deposit account to another limit tier (only admin can do this)
+pub fn set_user_tier(
+    ctx: Context<SetUserTier>,
+    user_id: String,
+    tier: u8,
+) -> Result<()> {
+    require!((tier as usize) < MAX_TIERS, ErrorCode::InvalidTier);
+
+    let user_pda = &mut ctx.accounts.user_pda;
+    let old_tier = user_pda.tier;
+    user_pda.tier = tier;
+
+    msg!("✅ User '{}' moved from tier {} to tier {}", user_id, old_tier, tier);
+
+    emit!(UserTierUpdated {
+        user_id,
+        old_tier,
+        new_tier: tier,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user_id: String)]
+pub struct SetUserTier<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit", user_id.as_bytes()],
+        bump = user_pda.bump,
+    )]
+    pub user_pda: Account<'info, UserPDA>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount, Mint},
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::utils::pda_transfer;
+
+/// Move tokens from one user deposit account to another
+/// (only the source account owner can do this).
+/// Only the unlocked balance can leave the source, and the transfer counts
+/// against the destination's tier limits like a deposit would.
+pub fn transfer_between_users(
+    ctx: Context<TransferBetweenUsers>,
+    from_user_id: String,
+    to_user_id: String,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(from_user_id != to_user_id, ErrorCode::SameUserTransfer);
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Source side: balance and lockups
+    require!(ctx.accounts.from_pda_ata.amount >= amount, ErrorCode::InsufficientBalance);
+    let available = ctx.accounts.from_pda.available_balance(
+        ctx.accounts.lock.as_deref(),
+        ctx.accounts.from_pda_ata.amount,
+        now,
+    )?;
+    require!(amount <= available, ErrorCode::FundsLocked);
+
+    // Destination side: tier caps over the rolling window
+    let limits = ctx
+        .accounts
+        .program_config
+        .tier_limits
+        .get(ctx.accounts.to_pda.tier as usize)
+        .copied()
+        .ok_or(ErrorCode::InvalidTier)?;
+    let balance = ctx.accounts.to_pda_ata.amount;
+    ctx.accounts.to_pda.record_deposit(&limits, balance, amount, now)?;
+
+    // Transfer, signed by the source PDA
+    let from_user_id_bytes = from_user_id.as_bytes();
+    let seeds = &[
+        b"deposit",
+        from_user_id_bytes,
+        &[ctx.accounts.from_pda.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.from_pda_ata.to_account_info(),
+        ctx.accounts.to_pda_ata.to_account_info(),
+        ctx.accounts.from_pda.to_account_info(),
+        signer_seeds,
+        amount,
+    )?;
+
+    msg!("✅ Transferred {} tokens from user '{}' to user '{}'", amount, from_user_id, to_user_id);
+
+    emit!(UserToUserTransfer {
+        from_user_id,
+        to_user_id,
+        amount,
+        authority: ctx.accounts.authority.key(),
+        from_ata: ctx.accounts.from_pda_ata.key(),
+        to_ata: ctx.accounts.to_pda_ata.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(from_user_id: String, to_user_id: String)]
+pub struct TransferBetweenUsers<'info> {
+    /// Holds the per-tier limits applied to the destination
+    #[account(
+        seeds = [b"config"],
+        bump = program_config.bump,
+        constraint = !program_config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        seeds = [b"deposit", from_user_id.as_bytes()],
+        bump = from_pda.bump,
+        constraint = from_pda.owner == authority.key() @ ErrorCode::UnauthorizedUserOwner,
+        constraint = !from_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub from_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = from_pda,
+    )]
+    pub from_pda_ata: Account<'info, TokenAccount>,
+
+    /// Source user's lock, required when `from_pda.has_lock`
+    #[account(
+        seeds = [b"lock", from_pda.key().as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Option<Account<'info, Lock>>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit", to_user_id.as_bytes()],
+        bump = to_pda.bump,
+        constraint = !to_pda.frozen @ ErrorCode::AccountFrozen,
+    )]
+    pub to_pda: Account<'info, UserPDA>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = to_pda,
+    )]
+    pub to_pda_ata: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Source account owner
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -1,7 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    token::{Token, TokenAccount, Mint},
-};
 use crate::state::*;
 use crate::errors::ErrorCode;
 use crate::events::*;
@@ -13,25 +10,14 @@ pub fn update_admin(
     ctx: Context<UpdateAdmin>,
     new_admin: Pubkey,
 ) -> Result<()> {
-    // Validate new admin is not zero address
-    require!(new_admin != Pubkey::default(), ErrorCode::InvalidNewAdmin);
-
     // Verify current admin authorization
     require!(
         ctx.accounts.admin_state.admin == ctx.accounts.current_admin.key(),
         ErrorCode::UnauthorizedAdmin
     );
 
-    // Prevent setting same admin
-    require!(
-        ctx.accounts.admin_state.admin != new_admin,
-        ErrorCode::SameAdminUpdate
-    );
-
-    let admin_state = &mut ctx.accounts.admin_state;
-    let old_admin = admin_state.admin;
-
-    admin_state.admin = new_admin;
+    // Rejects the zero address and the current admin
+    let old_admin = ctx.accounts.admin_state.set_admin(new_admin)?;
 
     msg!("✅ Admin updated from {} to {}", old_admin, new_admin);
 
@@ -50,6 +36,8 @@ pub struct UpdateAdmin<'info> {
         mut,
         seeds = [b"admin"],
         bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
     )]
     pub admin_state: Account<'info, AdminState>,
 
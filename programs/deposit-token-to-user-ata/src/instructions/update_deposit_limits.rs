@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Mint,
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Update the per-mint deposit limits (only admin can do this)
+/// A value of 0 disables the corresponding bound.
+pub fn update_deposit_limits(
+    ctx: Context<UpdateDepositLimits>,
+    min_deposit: u64,
+    max_deposit: u64,
+) -> Result<()> {
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    treasury_state.set_deposit_limits(min_deposit, max_deposit)?;
+
+    msg!("✅ Deposit limits for mint {} set to min {} / max {}",
+         treasury_state.token_mint, min_deposit, max_deposit);
+
+    emit!(DepositLimitsUpdated {
+        token_mint: treasury_state.token_mint,
+        min_deposit,
+        max_deposit,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateDepositLimits<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump = treasury_state.bump,
+        constraint = treasury_state.token_mint == mint.key() @ ErrorCode::InvalidTokenMint,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
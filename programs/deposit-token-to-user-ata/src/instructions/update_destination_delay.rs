@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Update the activation delay for new payout destinations (only admin can do this)
+/// Entries that were already added keep their `activates_at`.
+pub fn update_destination_delay(ctx: Context<UpdateDestinationDelay>, delay: i64) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let old_delay = program_config.destination_delay;
+    program_config.set_destination_delay(delay)?;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Destination activation delay set to {}s", delay);
+
+    emit!(DestinationDelayUpdated {
+        old_delay,
+        new_delay: delay,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateDestinationDelay<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Update the deposit fee and its recipient (only admin can do this)
+/// The fee can never exceed `MAX_FEE_BPS`.
+pub fn update_fee_config(
+    ctx: Context<UpdateFeeConfig>,
+    fee_bps: u16,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.set_fee_config(fee_bps, fee_recipient)?;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Deposit fee set to {} bps, recipient {}", fee_bps, fee_recipient);
+
+    emit!(FeeConfigUpdated {
+        fee_bps,
+        fee_recipient,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeConfig<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Set or rotate the KYC attester and threshold (only admin can do this)
+/// Attestations issued by a previous attester stop counting once it is rotated out.
+pub fn update_kyc_config(
+    ctx: Context<UpdateKycConfig>,
+    attester: Pubkey,
+    kyc_threshold: u64,
+    kyc_min_level: u8,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.set_kyc_config(attester, kyc_threshold, kyc_min_level)?;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ KYC attester {} required above {} (level {})", attester, kyc_threshold, kyc_min_level);
+
+    emit!(KycConfigUpdated {
+        attester,
+        kyc_threshold,
+        kyc_min_level,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateKycConfig<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
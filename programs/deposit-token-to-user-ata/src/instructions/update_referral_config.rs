@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Update the referral reward paid on referred deposits (only admin can do this)
+/// The reward can never exceed `MAX_REFERRAL_BPS`.
+pub fn update_referral_config(
+    ctx: Context<UpdateReferralConfig>,
+    referral_bps: u16,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.set_referral_bps(referral_bps)?;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Referral reward set to {} bps", referral_bps);
+
+    emit!(ReferralConfigUpdated {
+        referral_bps,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateReferralConfig<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Configure how admin sweeps are split between recipients (only admin can do this)
+/// Weights must add up to 10,000 bps. Use the treasury state address as the
+/// recipient for the treasury's share; an empty list sends everything to the treasury.
+pub fn update_sweep_splits(
+    ctx: Context<UpdateSweepSplits>,
+    splits: Vec<SweepSplit>,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.set_sweep_splits(&splits)?;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Sweep splits updated ({} recipients)", splits.len());
+
+    emit!(SweepSplitsUpdated {
+        splits,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateSweepSplits<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Update the balance cap and rolling daily limit of a tier (only admin can do this)
+/// A value of 0 disables the corresponding cap.
+pub fn update_tier_limits(
+    ctx: Context<UpdateTierLimits>,
+    tier: u8,
+    max_balance: u64,
+    daily_limit: u64,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.set_tier_limits(tier, max_balance, daily_limit)?;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Tier {} limits set to max balance {} / daily {}", tier, max_balance, daily_limit);
+
+    emit!(TierLimitsUpdated {
+        tier,
+        max_balance,
+        daily_limit,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateTierLimits<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Set or rotate the key that signs withdrawal vouchers (only admin can do this)
+/// With the multisig or timelock on, rotation goes through an `UpdateVoucherSigner` action.
+pub fn update_voucher_signer(ctx: Context<UpdateVoucherSigner>, voucher_signer: Pubkey) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let old_signer = program_config.voucher_signer;
+    program_config.voucher_signer = voucher_signer;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Voucher signer rotated: {} -> {}", old_signer, voucher_signer);
+
+    emit!(VoucherSignerUpdated {
+        old_signer,
+        new_signer: voucher_signer,
+        updated_by: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateVoucherSigner<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_state.bump,
+        constraint = !admin_state.multisig_enabled @ ErrorCode::MultisigRequired,
+        constraint = admin_state.timelock_delay == 0 @ ErrorCode::TimelockRequired,
+    )]
+    pub admin_state: Account<'info, AdminState>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        constraint = admin.key() == admin_state.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+}
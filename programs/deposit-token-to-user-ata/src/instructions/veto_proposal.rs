@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+
+/// Cancel a pending admin set proposal (only guardian can do this)
+/// The proposal account is closed and its rent returned to the proposer.
+pub fn veto_proposal(ctx: Context<VetoProposal>, proposal_id: u64) -> Result<()> {
+    require!(!ctx.accounts.proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+
+    msg!("🛡️ Proposal {} vetoed by the guardian", proposal_id);
+
+    emit!(ProposalVetoed {
+        proposal_id,
+        vetoed_by: ctx.accounts.guardian.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct VetoProposal<'info> {
+    #[account(
+        seeds = [b"guardian"],
+        bump = guardian_state.bump,
+    )]
+    pub guardian_state: Account<'info, GuardianState>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Receives the proposal rent, checked by address
+    #[account(
+        mut,
+        address = proposal.proposer,
+    )]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = guardian.key() == guardian_state.guardian @ ErrorCode::UnauthorizedGuardian
+    )]
+    pub guardian: Signer<'info>,
+}
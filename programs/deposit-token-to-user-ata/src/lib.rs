@@ -3,8 +3,11 @@ pub mod instructions;
 pub mod state;
 pub mod errors;
 mod events;
+mod utils;
+mod admin_actions;
 
 use instructions::*;
+use state::{AdminAction, LockSchedule, SweepSplit};
 declare_id!("29dme7kugTJtCNVkUboAiTfSZVMWFqp7v6LYUWuuN66R");
 //
 // #[program]
@@ -23,14 +26,18 @@ declare_id!("29dme7kugTJtCNVkUboAiTfSZVMWFqp7v6LYUWuuN66R");
 pub mod deposit_token_to_user_ata {
     use super::*;
 
-    // pub fn initiate_token(
-    //     ctx: Context<InitToken>, // Use the imported InitToken struct here
-    //     metadata: InitTokenParams,
-    //     sale_start_time: i64,
-    //     sale_end_time: i64,
-    // ) -> Result<()> {
-    //     instructions::initiate_token::initiate_token(ctx, metadata, sale_start_time, sale_end_time)
-    // }
+    #[allow(clippy::too_many_arguments)]
+    pub fn initiate_token(
+        ctx: Context<InitToken>, // Use the imported InitToken struct here
+        metadata: InitTokenParams,
+        sale_start_time: i64,
+        sale_end_time: i64,
+        price: u64,
+        soft_cap: u64,
+        hard_cap: u64,
+    ) -> Result<()> {
+        instructions::initiate_token::initiate_token(ctx, metadata, sale_start_time, sale_end_time, price, soft_cap, hard_cap)
+    }
 
     pub fn initialize_test(ctx: Context<InitializeTest>) -> Result<()> {
         instructions::initialize_test::initialize_test(ctx)
@@ -40,8 +47,8 @@ pub mod deposit_token_to_user_ata {
         instructions::initialize::initialize(ctx, token_mint)
     }
 
-    pub fn create_user_deposit_account(ctx: Context<CreateUserDepositAccount>, user_id: String) -> Result<CreateAccountResult> {
-        instructions::create_user_deposit_account::create_user_deposit_account(ctx, user_id)
+    pub fn create_user_deposit_account(ctx: Context<CreateUserDepositAccount>, user_id: String, referrer_id: Option<String>) -> Result<CreateAccountResult> {
+        instructions::create_user_deposit_account::create_user_deposit_account(ctx, user_id, referrer_id)
     }
 
     pub fn get_user_deposit_info(ctx: Context<GetUserDepositInfo>, ) -> Result<UserDepositInfo> {
@@ -52,8 +59,25 @@ pub mod deposit_token_to_user_ata {
         instructions::find_user_pda_address::find_user_pda_address(ctx, user_id)
     }
 
-    pub fn deposit_tokens(ctx: Context<DepositTokens>, user_id: String, amount: u64,) -> Result<()>  {
-        instructions::deposit_tokens::deposit_tokens(ctx, user_id, amount)
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_tokens(
+        ctx: Context<DepositTokens>,
+        user_id: String,
+        amount: u64,
+        reference: Option<[u8; 32]>,
+        memo: Option<String>,
+        nonce: Option<u64>,
+        lock_schedule: Option<LockSchedule>,
+    ) -> Result<()>  {
+        instructions::deposit_tokens::deposit_tokens(ctx, user_id, amount, reference, memo, nonce, lock_schedule)
+    }
+
+    pub fn get_user_balance_info(ctx: Context<GetUserBalanceInfo>) -> Result<UserBalanceInfo>  {
+        instructions::get_user_balance_info::get_user_balance_info(ctx)
+    }
+
+    pub fn close_deposit_receipt(ctx: Context<CloseDepositReceipt>) -> Result<()>  {
+        instructions::close_deposit_receipt::close_deposit_receipt(ctx)
     }
 
     pub fn get_admin_info(ctx: Context<GetAdminInfo>) -> Result<AdminInfo>  {
@@ -64,12 +88,259 @@ pub mod deposit_token_to_user_ata {
         instructions::update_admin::update_admin(ctx, new_admin)
     }
 
+    pub fn update_deposit_limits(
+        ctx: Context<UpdateDepositLimits>,
+        min_deposit: u64,
+        max_deposit: u64,
+    ) -> Result<()>  {
+        instructions::update_deposit_limits::update_deposit_limits(ctx, min_deposit, max_deposit)
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()>  {
+        instructions::initialize_config::initialize_config(ctx)
+    }
+
+    pub fn update_tier_limits(
+        ctx: Context<UpdateTierLimits>,
+        tier: u8,
+        max_balance: u64,
+        daily_limit: u64,
+    ) -> Result<()>  {
+        instructions::update_tier_limits::update_tier_limits(ctx, tier, max_balance, daily_limit)
+    }
+
+    pub fn set_user_tier(ctx: Context<SetUserTier>, user_id: String, tier: u8) -> Result<()>  {
+        instructions::set_user_tier::set_user_tier(ctx, user_id, tier)
+    }
+
+    pub fn migrate_user_pda(ctx: Context<MigrateUserPda>, user_id: String) -> Result<()>  {
+        instructions::migrate_user_pda::migrate_user_pda(ctx, user_id)
+    }
+
+    pub fn update_fee_config(
+        ctx: Context<UpdateFeeConfig>,
+        fee_bps: u16,
+        fee_recipient: Pubkey,
+    ) -> Result<()>  {
+        instructions::update_fee_config::update_fee_config(ctx, fee_bps, fee_recipient)
+    }
+
+    pub fn update_referral_config(ctx: Context<UpdateReferralConfig>, referral_bps: u16) -> Result<()>  {
+        instructions::update_referral_config::update_referral_config(ctx, referral_bps)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>, user_id: String) -> Result<()>  {
+        instructions::claim_referral_rewards::claim_referral_rewards(ctx, user_id)
+    }
+
+    pub fn update_sweep_splits(ctx: Context<UpdateSweepSplits>, splits: Vec<SweepSplit>) -> Result<()>  {
+        instructions::update_sweep_splits::update_sweep_splits(ctx, splits)
+    }
+
+    pub fn create_plan(
+        ctx: Context<CreatePlan>,
+        plan_id: u64,
+        price: u64,
+        period: i64,
+        grace_period: i64,
+    ) -> Result<()>  {
+        instructions::create_plan::create_plan(ctx, plan_id, price, period, grace_period)
+    }
+
+    pub fn create_subscription(ctx: Context<CreateSubscription>, user_id: String) -> Result<()>  {
+        instructions::create_subscription::create_subscription(ctx, user_id)
+    }
+
+    pub fn charge_subscription(ctx: Context<ChargeSubscription>, user_id: String) -> Result<()>  {
+        instructions::charge_subscription::charge_subscription(ctx, user_id)
+    }
+
+    pub fn change_plan(ctx: Context<ChangePlan>, user_id: String, refund_surplus: bool) -> Result<()>  {
+        instructions::change_plan::change_plan(ctx, user_id, refund_surplus)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>, user_id: String) -> Result<()>  {
+        instructions::cancel_subscription::cancel_subscription(ctx, user_id)
+    }
+
+    pub fn buy(ctx: Context<Buy>, user_id: String, amount: u64) -> Result<()>  {
+        instructions::buy::buy(ctx, user_id, amount)
+    }
+
+    pub fn finalize_sale(ctx: Context<FinalizeSale>) -> Result<()>  {
+        instructions::finalize_sale::finalize_sale(ctx)
+    }
+
+    pub fn refund_sale_purchase(ctx: Context<RefundSalePurchase>, user_id: String) -> Result<()>  {
+        instructions::refund_sale_purchase::refund_sale_purchase(ctx, user_id)
+    }
+
+    pub fn fund_escrow(ctx: Context<FundEscrow>, escrow_id: u64, buyer_id: String, amount: u64) -> Result<()>  {
+        instructions::fund_escrow::fund_escrow(ctx, escrow_id, buyer_id, amount)
+    }
+
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>, escrow_id: u64) -> Result<()>  {
+        instructions::release_escrow::release_escrow(ctx, escrow_id)
+    }
+
+    pub fn refund_escrow(ctx: Context<RefundEscrow>, escrow_id: u64) -> Result<()>  {
+        instructions::refund_escrow::refund_escrow(ctx, escrow_id)
+    }
+
+    pub fn resolve_escrow_dispute(ctx: Context<ResolveEscrowDispute>, escrow_id: u64, release_to_seller: bool) -> Result<()>  {
+        instructions::resolve_escrow_dispute::resolve_escrow_dispute(ctx, escrow_id, release_to_seller)
+    }
+
+    pub fn transfer_between_users(ctx: Context<TransferBetweenUsers>, from_user_id: String, to_user_id: String, amount: u64) -> Result<()>  {
+        instructions::transfer_between_users::transfer_between_users(ctx, from_user_id, to_user_id, amount)
+    }
+
+    pub fn refund_to_user(
+        ctx: Context<RefundToUser>,
+        user_id: String,
+        amount: u64,
+        reason_code: u16,
+        original_tx_ref: [u8; 64],
+    ) -> Result<()>  {
+        instructions::refund_to_user::refund_to_user(ctx, user_id, amount, reason_code, original_tx_ref)
+    }
+
+    pub fn redeem_voucher(ctx: Context<RedeemVoucher>, user_id: String, amount: u64, nonce: u64, expiry: i64) -> Result<()>  {
+        instructions::redeem_voucher::redeem_voucher(ctx, user_id, amount, nonce, expiry)
+    }
+
+    pub fn update_voucher_signer(ctx: Context<UpdateVoucherSigner>, voucher_signer: Pubkey) -> Result<()>  {
+        instructions::update_voucher_signer::update_voucher_signer(ctx, voucher_signer)
+    }
+
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        distribution_id: u64,
+        merkle_root: [u8; 32],
+        total: u64,
+        num_leaves: u32,
+        expires_at: i64,
+    ) -> Result<()>  {
+        instructions::create_distribution::create_distribution(ctx, distribution_id, merkle_root, total, num_leaves, expires_at)
+    }
+
+    pub fn claim(
+        ctx: Context<Claim>,
+        distribution_id: u64,
+        index: u32,
+        user_id: String,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()>  {
+        instructions::claim::claim(ctx, distribution_id, index, user_id, amount, proof)
+    }
+
+    pub fn clawback(ctx: Context<Clawback>, distribution_id: u64) -> Result<()>  {
+        instructions::clawback::clawback(ctx, distribution_id)
+    }
+
+    pub fn configure_admin_set(ctx: Context<ConfigureAdminSet>, members: Vec<Pubkey>, threshold: u8) -> Result<()>  {
+        instructions::configure_admin_set::configure_admin_set(ctx, members, threshold)
+    }
+
+    pub fn propose_action(ctx: Context<ProposeAction>, action: AdminAction) -> Result<()>  {
+        instructions::propose_action::propose_action(ctx, action)
+    }
+
+    pub fn approve_action(ctx: Context<ApproveAction>, proposal_id: u64) -> Result<()>  {
+        instructions::approve_action::approve_action(ctx, proposal_id)
+    }
+
+    pub fn execute_action(ctx: Context<ExecuteAction>, proposal_id: u64) -> Result<()>  {
+        instructions::execute_action::execute_action(ctx, proposal_id)
+    }
+
+    pub fn enable_timelock(ctx: Context<EnableTimelock>, delay: i64) -> Result<()>  {
+        instructions::enable_timelock::enable_timelock(ctx, delay)
+    }
+
+    pub fn queue_action(ctx: Context<QueueAction>, action: AdminAction) -> Result<()>  {
+        instructions::queue_action::queue_action(ctx, action)
+    }
+
+    pub fn cancel_queued_action(ctx: Context<CancelQueuedAction>, action_id: u64) -> Result<()>  {
+        instructions::cancel_queued_action::cancel_queued_action(ctx, action_id)
+    }
+
+    pub fn execute_queued_action(ctx: Context<ExecuteQueuedAction>, action_id: u64) -> Result<()>  {
+        instructions::execute_queued_action::execute_queued_action(ctx, action_id)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()>  {
+        instructions::set_guardian::set_guardian(ctx, guardian)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()>  {
+        instructions::set_paused::set_paused(ctx, paused)
+    }
+
+    pub fn set_user_frozen(ctx: Context<SetUserFrozen>, user_id: String, frozen: bool) -> Result<()>  {
+        instructions::set_user_frozen::set_user_frozen(ctx, user_id, frozen)
+    }
+
+    pub fn veto_proposal(ctx: Context<VetoProposal>, proposal_id: u64) -> Result<()>  {
+        instructions::veto_proposal::veto_proposal(ctx, proposal_id)
+    }
+
+    pub fn set_spending_limit(ctx: Context<SetSpendingLimit>, key: Pubkey, max_per_tx: u64, max_per_window: u64) -> Result<()>  {
+        instructions::set_spending_limit::set_spending_limit(ctx, key, max_per_tx, max_per_window)
+    }
+
+    pub fn add_allowed_destination(ctx: Context<AddAllowedDestination>, mint: Pubkey, owner: Pubkey) -> Result<()>  {
+        instructions::add_allowed_destination::add_allowed_destination(ctx, mint, owner)
+    }
+
+    pub fn remove_allowed_destination(ctx: Context<RemoveAllowedDestination>, mint: Pubkey, owner: Pubkey) -> Result<()>  {
+        instructions::remove_allowed_destination::remove_allowed_destination(ctx, mint, owner)
+    }
+
+    pub fn update_destination_delay(ctx: Context<UpdateDestinationDelay>, delay: i64) -> Result<()>  {
+        instructions::update_destination_delay::update_destination_delay(ctx, delay)
+    }
+
+    pub fn block_wallet(ctx: Context<BlockWallet>, wallet: Pubkey, reason_code: u16) -> Result<()>  {
+        instructions::block_wallet::block_wallet(ctx, wallet, reason_code)
+    }
+
+    pub fn unblock_wallet(ctx: Context<UnblockWallet>, wallet: Pubkey) -> Result<()>  {
+        instructions::unblock_wallet::unblock_wallet(ctx, wallet)
+    }
+
+    pub fn update_kyc_config(ctx: Context<UpdateKycConfig>, attester: Pubkey, kyc_threshold: u64, kyc_min_level: u8) -> Result<()>  {
+        instructions::update_kyc_config::update_kyc_config(ctx, attester, kyc_threshold, kyc_min_level)
+    }
+
+    pub fn attest_user(ctx: Context<AttestUser>, user_id: String, level: u8, expires_at: i64) -> Result<()>  {
+        instructions::attest_user::attest_user(ctx, user_id, level, expires_at)
+    }
+
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>, user_id: String) -> Result<()>  {
+        instructions::revoke_attestation::revoke_attestation(ctx, user_id)
+    }
+
+    pub fn migrate_admin_state(ctx: Context<MigrateAdminState>) -> Result<()>  {
+        instructions::migrate_admin_state::migrate_admin_state(ctx)
+    }
+
+    pub fn migrate_treasury_state(ctx: Context<MigrateTreasuryState>, token_mint: Pubkey) -> Result<()>  {
+        instructions::migrate_treasury_state::migrate_treasury_state(ctx, token_mint)
+    }
+
+    pub fn migrate_user_pdas<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateUserPdas<'info>>) -> Result<()>  {
+        instructions::migrate_user_pdas::migrate_user_pdas(ctx)
+    }
+
     pub fn get_treasury_info(ctx: Context<GetTreasuryInfo>) -> Result<TreasuryInfo>  {
         instructions::get_treasury_info::get_treasury_info(ctx)
     }
 
-    pub fn admin_transfer_to_treasury(
-        ctx: Context<AdminTransferToTreasury>,
+    pub fn admin_transfer_to_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AdminTransferToTreasury<'info>>,
         user_id: String,
         amount: u64,
     ) -> Result<()>  {
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{SweepSplit, MAX_ADMIN_SIGNERS, MAX_SWEEP_SPLITS};
+
+/// A privileged operation that can be proposed and approved by the admin set
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AdminAction {
+    /// Replace the single admin key
+    UpdateAdmin { new_admin: Pubkey },
+    /// Replace the admin set; an empty member list turns the multisig off
+    UpdateAdminSet { members: Vec<Pubkey>, threshold: u8 },
+    /// Pay `amount` from the `mint` treasury to the `destination` token account
+    TreasuryPayout { mint: Pubkey, destination: Pubkey, amount: u64 },
+    UpdateDepositLimits { mint: Pubkey, min_deposit: u64, max_deposit: u64 },
+    UpdateTierLimits { tier: u8, max_balance: u64, daily_limit: u64 },
+    UpdateFeeConfig { fee_bps: u16, fee_recipient: Pubkey },
+    UpdateReferralConfig { referral_bps: u16 },
+    UpdateSweepSplits { splits: Vec<SweepSplit> },
+    /// Change the timelock delay; only valid as a queued action
+    UpdateTimelockDelay { delay: i64 },
+    /// Replace the guardian key
+    SetGuardian { guardian: Pubkey },
+    /// Rotate the key that signs withdrawal vouchers
+    UpdateVoucherSigner { voucher_signer: Pubkey },
+    /// Pay `amount` from the `mint` treasury back into `user_id`'s deposit ATA
+    RefundToUser {
+        user_id: String,
+        mint: Pubkey,
+        amount: u64,
+        reason_code: u16,
+        original_tx_ref: [u8; 64],
+    },
+    /// Set the outflow caps for a sweeper key or treasury, creating the limit if needed
+    SetSpendingLimit { key: Pubkey, max_per_tx: u64, max_per_window: u64 },
+    UpdateDestinationDelay { delay: i64 },
+    UpdateKycConfig { attester: Pubkey, kyc_threshold: u64, kyc_min_level: u8 },
+}
+
+impl AdminAction {
+    /// Serialized size of the largest variant
+    pub const MAX_SPACE: usize = 1 + 4 + 32 * MAX_ADMIN_SIGNERS + 1; // tag + members + threshold
+
+    /// Reject actions that would not fit in `MAX_SPACE`
+    pub fn fits(&self) -> bool {
+        match self {
+            AdminAction::UpdateAdminSet { members, .. } => members.len() <= MAX_ADMIN_SIGNERS,
+            AdminAction::UpdateSweepSplits { splits } => splits.len() <= MAX_SWEEP_SPLITS,
+            AdminAction::RefundToUser { user_id, .. } => user_id.len() <= 50,
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Largest number of signers in the admin set
+pub const MAX_ADMIN_SIGNERS: usize = 10;
+
+/// How long a proposal stays open for approvals and execution
+pub const PROPOSAL_LIFETIME: i64 = 7 * 24 * 60 * 60; // 7 days
+
+/// M-of-N admin set. While `AdminState::multisig_enabled` is set, privileged
+/// actions only run through an approved `Proposal`.
+#[account]
+pub struct AdminSet {
+    pub members: Vec<Pubkey>,      // Signers allowed to propose and approve
+    pub threshold: u8,             // Approvals needed to execute
+    pub version: u32,              // Bumped on every change, invalidating open proposals
+    pub proposal_count: u64,       // Next proposal id
+    pub bump: u8,                  // PDA bump
+}
+
+impl AdminSet {
+    pub const SPACE: usize = 8 + 4 + 32 * MAX_ADMIN_SIGNERS + 1 + 4 + 8 + 1; // discriminator + members + threshold + version + proposal count + bump
+
+    /// Check an M-of-N configuration: 1 <= M <= N <= MAX_ADMIN_SIGNERS, no duplicates
+    pub fn validate(members: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !members.is_empty() && members.len() <= MAX_ADMIN_SIGNERS,
+            ErrorCode::InvalidAdminSet
+        );
+        require!(
+            threshold >= 1 && threshold as usize <= members.len(),
+            ErrorCode::InvalidAdminSet
+        );
+        for (i, member) in members.iter().enumerate() {
+            require!(*member != Pubkey::default(), ErrorCode::InvalidAdminSet);
+            require!(!members[..i].contains(member), ErrorCode::InvalidAdminSet);
+        }
+        Ok(())
+    }
+
+    /// Replace the members and threshold
+    pub fn set_members(&mut self, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        Self::validate(&members, threshold)?;
+        self.members = members;
+        self.threshold = threshold;
+        self.version = self.version.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Index of `key` in the member list
+    pub fn member_index(&self, key: &Pubkey) -> Result<usize> {
+        self.members
+            .iter()
+            .position(|member| member == key)
+            .ok_or_else(|| error!(ErrorCode::NotAdminSetMember))
+    }
+}
@@ -1,12 +1,28 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
 
 #[account]
 pub struct AdminState {
     pub admin: Pubkey,          // Current admin wallet
     pub bump: u8,               // PDA bump
     pub created_at: i64,        // When admin was initialized
+    pub multisig_enabled: bool, // Privileged actions go through `AdminSet` proposals
+    pub timelock_delay: i64,    // Seconds a queued action waits before it can run; 0 = no timelock
+    pub timelock_count: u64,    // Number of actions queued so far (next queued action id)
+    pub version: u8,            // Layout version, see `ACCOUNT_VERSION`
+    pub reserved: [u8; 14],     // Room for future fields, zeroed
 }
 
 impl AdminState {
-    pub const SPACE: usize = 8 + 32 + 1 + 8 + 32; // discriminator + pubkey + bump + timestamp
+    pub const SPACE: usize = 8 + 32 + 1 + 8 + 1 + 8 + 8 + 1 + 14; // discriminator + pubkey + bump + timestamp + multisig flag + timelock delay + queue counter + version + reserved
+
+    /// Replace the admin, returning the previous one
+    pub fn set_admin(&mut self, new_admin: Pubkey) -> Result<Pubkey> {
+        require!(new_admin != Pubkey::default(), ErrorCode::InvalidNewAdmin);
+        require!(self.admin != new_admin, ErrorCode::SameAdminUpdate);
+
+        let old_admin = self.admin;
+        self.admin = new_admin;
+        Ok(old_admin)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// Longest activation delay that can be configured for new payout destinations
+pub const MAX_DESTINATION_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// Default activation delay for new payout destinations
+pub const DEFAULT_DESTINATION_DELAY: i64 = 24 * 60 * 60;
+
+/// A wallet that treasury payouts in `mint` may be sent to, usable from `activates_at`
+#[account]
+pub struct AllowedDestination {
+    pub mint: Pubkey,              // Treasury mint
+    pub owner: Pubkey,             // Owner of the destination token account
+    pub added_by: Pubkey,          // Admin who added it (receives the rent on removal)
+    pub activates_at: i64,         // Payouts are rejected before this time
+    pub created_at: i64,           // When it was added
+    pub bump: u8,                  // PDA bump
+}
+
+impl AllowedDestination {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1; // discriminator + mint + owner + added by + 2 timestamps + bump
+
+    pub fn is_active(&self, now: i64) -> bool {
+        now >= self.activates_at
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// KYC attestation for a user deposit account (`["attestation", user_pda]`),
+/// written by the attester configured in `ProgramConfig`
+#[account]
+pub struct Attestation {
+    pub user_pda: Pubkey,          // Attested deposit account
+    pub attester: Pubkey,          // Key that issued it
+    pub level: u8,                 // KYC level, compared with `ProgramConfig::kyc_min_level`
+    pub issued_at: i64,            // When it was (re)issued
+    pub expires_at: i64,           // No longer valid from this time
+    pub revoked: bool,             // Revoked by the admin
+    pub bump: u8,                  // PDA bump
+}
+
+impl Attestation {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 1 + 1; // discriminator + user pda + attester + level + 2 timestamps + revoked + bump
+
+    /// Whether the attestation is current, unrevoked, at least `min_level` and
+    /// issued by the currently configured `attester`
+    pub fn is_valid(&self, attester: &Pubkey, min_level: u8, now: i64) -> bool {
+        !self.revoked && self.attester == *attester && self.level >= min_level && now < self.expires_at
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// A wallet whose deposits are rejected (`["blocked", wallet]`)
+#[account]
+pub struct BlockedWallet {
+    pub wallet: Pubkey,            // Blocked depositor
+    pub reason_code: u16,          // Compliance reason, defined off-chain
+    pub blocked_by: Pubkey,        // Admin who added the entry
+    pub created_at: i64,           // When it was blocked
+    pub bump: u8,                  // PDA bump
+}
+
+impl BlockedWallet {
+    pub const SPACE: usize = 8 + 32 + 2 + 32 + 8 + 1; // discriminator + wallet + reason + blocked by + timestamp + bump
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Retry-protection record for a deposit `nonce` (`["receipt", user_pda, nonce]`)
+#[account]
+pub struct DepositReceipt {
+    pub user_pda: Pubkey,          // Deposit account that was credited
+    pub depositor: Pubkey,         // Who signed the deposit
+    pub amount: u64,               // Amount credited
+    pub reference: [u8; 32],       // Client reference (invoice / order id)
+    pub nonce: u64,                // Client retry nonce
+    pub created_at: i64,           // When the deposit landed
+    pub bump: u8,                  // PDA bump
+}
+
+impl DepositReceipt {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 32 + 8 + 8 + 1; // discriminator + 2 pubkeys + amount + reference + nonce + timestamp + bump
+
+    /// How long a receipt must be kept before its rent can be reclaimed
+    pub const RETENTION_PERIOD: i64 = 30 * 24 * 60 * 60; // 30 days
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Largest number of leaves a distribution can track (bitmap of 8,000 bytes)
+pub const MAX_DISTRIBUTION_LEAVES: u32 = 64_000;
+
+#[account]
+pub struct Distribution {
+    pub distribution_id: u64,      // Admin-assigned distribution identifier
+    pub mint: Pubkey,              // Token being distributed
+    pub merkle_root: [u8; 32],     // Root over (index, user_id, amount) leaves
+    pub total: u64,                // Amount moved into the vault
+    pub claimed: u64,              // Amount claimed so far
+    pub num_leaves: u32,           // Number of leaves in the tree
+    pub expires_at: i64,           // Unclaimed funds can be clawed back after this time
+    pub created_at: i64,           // When the distribution was created
+    pub clawed_back: bool,         // Remaining funds returned to the treasury
+    pub bump: u8,                  // PDA bump
+    pub claimed_bitmap: Vec<u8>,   // One bit per leaf, set once claimed
+}
+
+impl Distribution {
+    pub fn space(num_leaves: u32) -> usize {
+        8 +                        // discriminator
+            8 +                        // distribution_id
+            32 +                       // mint
+            32 +                       // merkle_root
+            8 +                        // total
+            8 +                        // claimed
+            4 +                        // num_leaves
+            8 +                        // expires_at
+            8 +                        // created_at
+            1 +                        // clawed_back
+            1 +                        // bump
+            4 + Self::bitmap_len(num_leaves) // claimed_bitmap
+    }
+
+    pub fn bitmap_len(num_leaves: u32) -> usize {
+        num_leaves.div_ceil(8) as usize
+    }
+
+    /// Mark leaf `index` as claimed, failing if it already was
+    pub fn set_claimed(&mut self, index: u32) -> Result<()> {
+        require!(index < self.num_leaves, ErrorCode::InvalidMerkleProof);
+        let byte = &mut self.claimed_bitmap[(index / 8) as usize];
+        let mask = 1u8 << (index % 8);
+        require!(*byte & mask == 0, ErrorCode::AlreadyClaimed);
+        *byte |= mask;
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EscrowStatus {
+    Funded,                        // Holding the buyer's funds
+    Released,                      // Paid out to the seller
+    Refunded,                      // Returned to the buyer
+}
+
+#[account]
+pub struct Escrow {
+    pub escrow_id: u64,            // Client-assigned id, unique per buyer and seller pair
+    pub buyer_pda: Pubkey,         // Deposit account that funded the escrow
+    pub seller_pda: Pubkey,        // Deposit account that receives it on release
+    pub mint: Pubkey,              // Token held in escrow
+    pub amount: u64,               // Amount held
+    pub status: EscrowStatus,      // Funded / Released / Refunded
+    pub created_at: i64,           // When the escrow was funded
+    pub settled_at: i64,           // When it was released or refunded (0 while funded)
+    pub bump: u8,                  // PDA bump
+}
+
+impl Escrow {
+    pub const SPACE: usize = 8 + 8 + 32 + 32 + 32 + 8 + 1 + 8 + 8 + 1; // discriminator + id + 3 pubkeys + amount + status + 2 timestamps + bump
+}
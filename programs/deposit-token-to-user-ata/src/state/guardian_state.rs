@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Holds the guardian key next to `AdminState`. The guardian can pause the
+/// program, freeze users and cancel pending admin actions, but cannot move funds.
+#[account]
+pub struct GuardianState {
+    pub guardian: Pubkey,       // Current guardian wallet
+    pub bump: u8,               // PDA bump
+    pub updated_at: i64,        // When the guardian was last set
+}
+
+impl GuardianState {
+    pub const SPACE: usize = 8 + 32 + 1 + 8; // discriminator + pubkey + bump + timestamp
+
+    /// Replace the guardian, returning the previous one
+    pub fn set_guardian(&mut self, guardian: Pubkey, now: i64) -> Result<Pubkey> {
+        require!(guardian != Pubkey::default(), ErrorCode::InvalidGuardian);
+
+        let old_guardian = self.guardian;
+        self.guardian = guardian;
+        self.updated_at = now;
+        Ok(old_guardian)
+    }
+
+    /// Whether `key` may halt the program: the admin, or the guardian once one is set
+    pub fn can_halt(guardian_state: Option<&Self>, key: &Pubkey, admin: &Pubkey) -> bool {
+        key == admin || guardian_state.is_some_and(|state| *key == state.guardian)
+    }
+
+    /// Whether `key` may lift a pause or freeze. This is left to the guardian
+    /// alone, so nothing can be resumed until a guardian has been set.
+    pub fn can_resume(guardian_state: Option<&Self>, key: &Pubkey) -> bool {
+        guardian_state.is_some_and(|state| *key == state.guardian)
+    }
+}
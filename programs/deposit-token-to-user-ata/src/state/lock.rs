@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+#[account]
+pub struct Lock {
+    pub user_pda: Pubkey,          // Deposit account the lock applies to
+    pub amount: u64,               // Amount locked by the current schedule
+    pub unlock_at: i64,            // When everything is unlocked
+    pub vesting_start: i64,        // Start of linear vesting (0 = cliff at `unlock_at`)
+    pub created_at: i64,           // When the lock was first created
+    pub bump: u8,                  // PDA bump
+}
+
+impl Lock {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1; // discriminator + user_pda + amount + unlock_at + vesting_start + created_at + bump
+
+    /// Portion of `amount` that is still locked at `now`
+    pub fn locked_amount(&self, now: i64) -> u64 {
+        if now >= self.unlock_at {
+            return 0;
+        }
+        if self.vesting_start == 0 || now <= self.vesting_start {
+            return self.amount;
+        }
+        // Linear release between vesting_start and unlock_at
+        let remaining = (self.unlock_at - now) as u128;
+        let duration = (self.unlock_at - self.vesting_start) as u128;
+        (self.amount as u128 * remaining / duration) as u64
+    }
+
+    /// Lock `amount` more under `schedule`. Whatever is still locked is carried
+    /// over and the later unlock date wins. If either schedule is a cliff the
+    /// lock stays a cliff, otherwise the later vesting start wins, so adding to
+    /// a lock never releases funds early.
+    pub fn add(&mut self, amount: u64, schedule: &LockSchedule, now: i64) -> Result<()> {
+        require!(schedule.unlock_at > now, ErrorCode::InvalidLockSchedule);
+        require!(
+            schedule.vesting_start == 0 || schedule.vesting_start < schedule.unlock_at,
+            ErrorCode::InvalidLockSchedule
+        );
+
+        let still_locked = self.locked_amount(now);
+        self.amount = still_locked
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        // Vesting never starts before the deposit that created it
+        let new_vesting_start = if schedule.vesting_start == 0 {
+            0
+        } else {
+            schedule.vesting_start.max(now)
+        };
+        if still_locked == 0 {
+            // Nothing left under the old schedule
+            self.unlock_at = schedule.unlock_at;
+            self.vesting_start = new_vesting_start;
+        } else {
+            self.unlock_at = self.unlock_at.max(schedule.unlock_at);
+            self.vesting_start = if self.vesting_start == 0 || new_vesting_start == 0 {
+                0
+            } else {
+                self.vesting_start.max(new_vesting_start)
+            };
+        }
+        Ok(())
+    }
+}
+
+/// Lock terms passed with `deposit_tokens`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LockSchedule {
+    pub unlock_at: i64,            // Everything is released at this time
+    pub vesting_start: i64,        // Linear release from this time (0 = cliff)
+}
@@ -1,3 +1,7 @@
+/// Layout version written to `UserPDA`, `AdminState` and `TreasuryState`.
+/// Accounts created before versioning read as 0 until migrated.
+pub const ACCOUNT_VERSION: u8 = 1;
+
 pub mod admin_state;
 pub use admin_state::*;
 
@@ -6,4 +10,64 @@ pub use treasury_state::*;
 
 
 pub mod user_pda;
-pub use user_pda::*;
\ No newline at end of file
+pub use user_pda::*;
+
+pub mod deposit_receipt;
+pub use deposit_receipt::*;
+
+pub mod reference_receipt;
+pub use reference_receipt::*;
+
+pub mod program_config;
+pub use program_config::*;
+
+pub mod plan;
+pub use plan::*;
+
+pub mod subscription;
+pub use subscription::*;
+
+pub mod sale;
+pub use sale::*;
+
+pub mod sale_purchase;
+pub use sale_purchase::*;
+
+pub mod lock;
+pub use lock::*;
+
+pub mod escrow;
+pub use escrow::*;
+
+pub mod voucher;
+pub use voucher::*;
+
+pub mod distribution;
+pub use distribution::*;
+
+pub mod admin_set;
+pub use admin_set::*;
+
+pub mod admin_action;
+pub use admin_action::*;
+
+pub mod proposal;
+pub use proposal::*;
+
+pub mod queued_action;
+pub use queued_action::*;
+
+pub mod guardian_state;
+pub use guardian_state::*;
+
+pub mod spending_limit;
+pub use spending_limit::*;
+
+pub mod allowed_destination;
+pub use allowed_destination::*;
+
+pub mod blocked_wallet;
+pub use blocked_wallet::*;
+
+pub mod attestation;
+pub use attestation::*;
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Plan {
+    pub plan_id: u64,              // Admin-assigned plan identifier
+    pub mint: Pubkey,              // Token the plan is billed in
+    pub price: u64,                // Amount charged per period
+    pub period: i64,               // Billing period in seconds
+    pub grace_period: i64,         // Seconds a lapsed subscription is retried before going inactive
+    pub created_at: i64,           // When the plan was created
+    pub bump: u8,                  // PDA bump
+}
+
+impl Plan {
+    pub const SPACE: usize = 8 + 8 + 32 + 8 + 8 + 8 + 8 + 1; // discriminator + id + mint + price + period + grace period + timestamp + bump
+}
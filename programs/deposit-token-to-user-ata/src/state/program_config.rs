@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{Attestation, MAX_DESTINATION_DELAY};
+
+/// Number of user tiers that can be configured
+pub const MAX_TIERS: usize = 4;
+
+/// Length of the rolling deposit window in seconds
+pub const DEPOSIT_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+/// Basis point denominator (100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Hard ceiling on the deposit fee (5%)
+pub const MAX_FEE_BPS: u16 = 500;
+
+/// Hard ceiling on the referral reward (10%)
+pub const MAX_REFERRAL_BPS: u16 = 1_000;
+
+/// Maximum number of recipients a sweep can be split between
+pub const MAX_SWEEP_SPLITS: usize = 4;
+
+/// Program-wide configuration. Amounts are denominated in the treasury mint.
+#[account]
+pub struct ProgramConfig {
+    pub tier_limits: [TierLimits; MAX_TIERS], // Limits indexed by `UserPDA::tier`
+    pub fee_bps: u16,                          // Deposit fee in basis points
+    pub fee_recipient: Pubkey,                 // Owner of the fee vault ATA
+    pub sweep_splits: [SweepSplit; MAX_SWEEP_SPLITS], // How admin sweeps are divided
+    pub sweep_split_count: u8,                 // Number of active entries in `sweep_splits`
+    pub referral_bps: u16,                     // Referrer reward on referred deposits, in basis points
+    pub paused: bool,                          // Deposits, sweeps, transfers and payouts are halted
+    pub destination_delay: i64,                // Seconds before a new `AllowedDestination` can be paid
+    pub attester: Pubkey,                      // Key allowed to write KYC `Attestation`s
+    pub kyc_threshold: u64,                    // Deposits and withdrawals above this need an attestation; 0 = off
+    pub kyc_min_level: u8,                     // Lowest attestation level accepted
+    pub voucher_signer: Pubkey,                // Key that signs withdrawal vouchers; default = vouchers off
+    pub bump: u8,                              // PDA bump
+    pub updated_at: i64,                       // Last time the config changed
+}
+
+impl ProgramConfig {
+    pub const SPACE: usize = 8 + TierLimits::SPACE * MAX_TIERS + 2 + 32 + SweepSplit::SPACE * MAX_SWEEP_SPLITS + 1 + 2 + 1 + 8 + 32 + 8 + 1 + 32 + 1 + 8; // discriminator + tier limits + fee bps + fee recipient + sweep splits + split count + referral bps + paused + destination delay + attester + kyc threshold + kyc level + voucher signer + bump + timestamp
+
+    /// Set the balance cap and daily limit of `tier`; 0 disables a cap
+    pub fn set_tier_limits(&mut self, tier: u8, max_balance: u64, daily_limit: u64) -> Result<()> {
+        require!((tier as usize) < MAX_TIERS, ErrorCode::InvalidTier);
+        self.tier_limits[tier as usize] = TierLimits {
+            max_balance,
+            daily_limit,
+        };
+        Ok(())
+    }
+
+    /// Set the deposit fee and its recipient, capped at `MAX_FEE_BPS`
+    pub fn set_fee_config(&mut self, fee_bps: u16, fee_recipient: Pubkey) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, ErrorCode::FeeTooHigh);
+        require!(
+            fee_bps == 0 || fee_recipient != Pubkey::default(),
+            ErrorCode::InvalidFeeRecipient
+        );
+        self.fee_bps = fee_bps;
+        self.fee_recipient = fee_recipient;
+        Ok(())
+    }
+
+    /// Set the activation delay for new payout destinations
+    pub fn set_destination_delay(&mut self, delay: i64) -> Result<()> {
+        require!((0..=MAX_DESTINATION_DELAY).contains(&delay), ErrorCode::InvalidDestinationDelay);
+        self.destination_delay = delay;
+        Ok(())
+    }
+
+    /// Set the KYC attester and the amount above which an attestation is required
+    pub fn set_kyc_config(&mut self, attester: Pubkey, kyc_threshold: u64, kyc_min_level: u8) -> Result<()> {
+        require!(
+            kyc_threshold == 0 || attester != Pubkey::default(),
+            ErrorCode::InvalidAttester
+        );
+        self.attester = attester;
+        self.kyc_threshold = kyc_threshold;
+        self.kyc_min_level = kyc_min_level;
+        Ok(())
+    }
+
+    /// Require a valid attestation when `amount` is above the KYC threshold
+    pub fn check_kyc(&self, attestation: Option<&Attestation>, amount: u64, now: i64) -> Result<()> {
+        if self.kyc_threshold == 0 || amount <= self.kyc_threshold {
+            return Ok(());
+        }
+        require!(
+            attestation.is_some_and(|attestation| attestation.is_valid(&self.attester, self.kyc_min_level, now)),
+            ErrorCode::KycRequired
+        );
+        Ok(())
+    }
+
+    /// Set the referral reward, capped at `MAX_REFERRAL_BPS`
+    pub fn set_referral_bps(&mut self, referral_bps: u16) -> Result<()> {
+        require!(referral_bps <= MAX_REFERRAL_BPS, ErrorCode::ReferralRewardTooHigh);
+        self.referral_bps = referral_bps;
+        Ok(())
+    }
+
+    /// Replace the sweep splits. Weights must add up to 10,000 bps unless
+    /// `splits` is empty, which sends everything to the treasury.
+    pub fn set_sweep_splits(&mut self, splits: &[SweepSplit]) -> Result<()> {
+        require!(splits.len() <= MAX_SWEEP_SPLITS, ErrorCode::TooManySplits);
+
+        let mut total_bps: u64 = 0;
+        for split in splits {
+            require!(split.bps > 0, ErrorCode::InvalidSplitConfig);
+            require!(split.recipient != Pubkey::default(), ErrorCode::InvalidSplitConfig);
+            total_bps += split.bps as u64;
+        }
+        require!(
+            splits.is_empty() || total_bps == BPS_DENOMINATOR,
+            ErrorCode::InvalidSplitConfig
+        );
+
+        self.sweep_splits = [SweepSplit::default(); MAX_SWEEP_SPLITS];
+        self.sweep_splits[..splits.len()].copy_from_slice(splits);
+        self.sweep_split_count = splits.len() as u8;
+        Ok(())
+    }
+
+    /// Fee charged on a deposit of `amount`, rounded down
+    pub fn deposit_fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(fee as u64)
+    }
+
+    /// Referral reward earned on a credited deposit of `amount`, rounded down
+    pub fn referral_reward(&self, amount: u64) -> Result<u64> {
+        let reward = (amount as u128)
+            .checked_mul(self.referral_bps as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(reward as u64)
+    }
+
+    /// Divide a swept `amount` between the configured split recipients.
+    /// Without splits everything goes to `treasury`. The last leg absorbs
+    /// rounding so the legs always add up to `amount`.
+    pub fn sweep_legs(&self, amount: u64, treasury: Pubkey) -> Result<Vec<(SweepSplit, u64)>> {
+        if self.sweep_split_count == 0 {
+            let split = SweepSplit {
+                recipient: treasury,
+                bps: BPS_DENOMINATOR as u16,
+            };
+            return Ok(vec![(split, amount)]);
+        }
+
+        let splits = &self.sweep_splits[..self.sweep_split_count as usize];
+        let mut legs = Vec::with_capacity(splits.len());
+        let mut remaining = amount;
+        for (i, split) in splits.iter().enumerate() {
+            let leg_amount = if i + 1 == splits.len() {
+                remaining
+            } else {
+                ((amount as u128) * (split.bps as u128) / (BPS_DENOMINATOR as u128)) as u64
+            };
+            remaining = remaining
+                .checked_sub(leg_amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            legs.push((*split, leg_amount));
+        }
+        Ok(legs)
+    }
+
+    /// Number of split legs paid to somewhere other than `treasury`. Each of
+    /// them needs its destination ATA passed in `remaining_accounts`.
+    pub fn external_sweep_leg_count(&self, treasury: Pubkey) -> usize {
+        self.sweep_splits[..self.sweep_split_count as usize]
+            .iter()
+            .filter(|split| split.recipient != treasury)
+            .count()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TierLimits {
+    pub max_balance: u64,       // Cap on the deposit account balance (0 = no cap)
+    pub daily_limit: u64,       // Cap on deposits per rolling window (0 = no cap)
+}
+
+impl TierLimits {
+    pub const SPACE: usize = 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct SweepSplit {
+    pub recipient: Pubkey,      // Wallet (or treasury state) receiving this share
+    pub bps: u16,               // Share in basis points
+}
+
+impl SweepSplit {
+    pub const SPACE: usize = 32 + 2;
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::AdminAction;
+
+#[account]
+pub struct Proposal {
+    pub proposal_id: u64,          // Sequential id from `AdminSet::proposal_count`
+    pub proposer: Pubkey,          // Member who proposed it
+    pub action: AdminAction,       // What runs once approved
+    pub set_version: u32,          // `AdminSet::version` the approvals refer to
+    pub approvals: u16,            // Bitmap of approving member indices
+    pub approval_count: u8,        // Number of approvals
+    pub created_at: i64,           // When it was proposed
+    pub expires_at: i64,           // Cannot be approved or executed after this time
+    pub executed: bool,            // Already executed
+    pub bump: u8,                  // PDA bump
+}
+
+impl Proposal {
+    pub const SPACE: usize = 8 + 8 + 32 + AdminAction::MAX_SPACE + 4 + 2 + 1 + 8 + 8 + 1 + 1; // discriminator + id + proposer + action + set version + approvals + count + 2 timestamps + executed + bump
+
+    /// Record an approval from the member at `index`
+    pub fn approve(&mut self, index: usize) -> Result<()> {
+        let mask = 1u16 << index;
+        require!(self.approvals & mask == 0, ErrorCode::ProposalAlreadyApproved);
+        self.approvals |= mask;
+        self.approval_count += 1;
+        Ok(())
+    }
+}
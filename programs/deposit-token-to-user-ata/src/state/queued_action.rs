@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::AdminAction;
+
+/// Longest timelock delay that can be configured
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+#[account]
+pub struct QueuedAction {
+    pub action_id: u64,            // Sequential id from `AdminState::timelock_count`
+    pub action: AdminAction,       // What runs once the delay has passed
+    pub queued_by: Pubkey,         // Admin who queued it
+    pub eta: i64,                  // Earliest time it can be executed
+    pub created_at: i64,           // When it was queued
+    pub bump: u8,                  // PDA bump
+}
+
+impl QueuedAction {
+    pub const SPACE: usize = 8 + 8 + AdminAction::MAX_SPACE + 32 + 8 + 8 + 1; // discriminator + id + action + queued by + 2 timestamps + bump
+}
+
+/// Check a timelock delay; 0 turns the timelock off
+pub fn validate_timelock_delay(delay: i64) -> Result<()> {
+    require!((0..=MAX_TIMELOCK_DELAY).contains(&delay), ErrorCode::InvalidTimelockDelay);
+    Ok(())
+}
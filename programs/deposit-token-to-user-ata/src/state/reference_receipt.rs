@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Record of a deposit `reference`. Unlike `DepositReceipt` it is never
+/// closed, so a reference can only be credited once.
+#[account]
+pub struct ReferenceReceipt {
+    pub user_pda: Pubkey,          // Deposit account that was credited
+    pub depositor: Pubkey,         // Who signed the deposit
+    pub amount: u64,               // Amount credited
+    pub reference: [u8; 32],       // Client reference (invoice / order id)
+    pub nonce: u64,                // Client retry nonce, if one was passed
+    pub created_at: i64,           // When the deposit landed
+    pub bump: u8,                  // PDA bump
+}
+
+impl ReferenceReceipt {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 32 + 8 + 8 + 1; // discriminator + 2 pubkeys + amount + reference + nonce + timestamp + bump
+}
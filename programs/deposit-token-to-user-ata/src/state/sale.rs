@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SaleStatus {
+    Active,                        // Accepting purchases inside the sale window
+    Succeeded,                     // Soft cap reached, tokens are final
+    Failed,                        // Soft cap missed, buyers can be refunded
+}
+
+#[account]
+pub struct Sale {
+    pub mint: Pubkey,              // Token being sold (sale PDA is its mint authority)
+    pub payment_mint: Pubkey,      // Token buyers pay with (a treasury mint)
+    pub price: u64,                // Payment base units per whole sale token
+    pub start_time: i64,           // Sale opens
+    pub end_time: i64,             // Sale closes
+    pub soft_cap: u64,             // Minimum payment raised for the sale to succeed
+    pub hard_cap: u64,             // Maximum sale tokens (base units) that can be sold
+    pub sold: u64,                 // Sale tokens minted so far
+    pub raised: u64,               // Payment collected so far
+    pub status: SaleStatus,        // Current sale status
+    pub created_at: i64,           // When the sale was created
+    pub bump: u8,                  // PDA bump
+}
+
+impl Sale {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 1; // discriminator + 2 pubkeys + price + window + caps + totals + status + timestamp + bump
+
+    /// Payment owed for `amount` sale base units, rounded up
+    pub fn cost(&self, amount: u64, decimals: u8) -> Result<u64> {
+        let unit = 10u128.pow(decimals as u32);
+        let cost = (amount as u128)
+            .checked_mul(self.price as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .div_ceil(unit);
+        u64::try_from(cost).map_err(|_| ErrorCode::ArithmeticOverflow.into())
+    }
+}
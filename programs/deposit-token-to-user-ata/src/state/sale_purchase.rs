@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct SalePurchase {
+    pub sale: Pubkey,              // Sale the tokens were bought in
+    pub user_pda: Pubkey,          // Deposit account holding the bought tokens
+    pub tokens: u64,               // Sale tokens minted to the user
+    pub paid: u64,                 // Payment taken into the treasury
+    pub bump: u8,                  // PDA bump
+}
+
+impl SalePurchase {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1; // discriminator + 2 pubkeys + tokens + paid + bump
+}
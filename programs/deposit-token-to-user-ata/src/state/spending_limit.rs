@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::DEPOSIT_WINDOW_SECONDS;
+
+/// Caps how much a key can move out per transaction and per rolling 24h
+/// window. Sweeps are limited per signing key, treasury outflows per
+/// `TreasuryState`. Outflows fail when the key has no limit account.
+#[account]
+pub struct SpendingLimit {
+    pub key: Pubkey,               // Sweeper key or treasury the limit applies to
+    pub max_per_tx: u64,           // Largest single movement; 0 = no cap
+    pub max_per_window: u64,       // Total allowed per window; 0 = no cap
+    pub window_start: i64,         // Start of the current window
+    pub window_spent: u64,         // Amount moved in the current window
+    pub bump: u8,                  // PDA bump
+    pub updated_at: i64,           // Last time the caps changed
+}
+
+impl SpendingLimit {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1 + 8; // discriminator + key + 2 caps + window start + window spent + bump + timestamp
+
+    /// Set the caps; a per-transaction cap above the window cap is rejected
+    pub fn set_caps(&mut self, max_per_tx: u64, max_per_window: u64, now: i64) -> Result<()> {
+        require!(
+            max_per_tx == 0 || max_per_window == 0 || max_per_tx <= max_per_window,
+            ErrorCode::InvalidSpendingLimit
+        );
+        self.max_per_tx = max_per_tx;
+        self.max_per_window = max_per_window;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Whether the new caps allow no more than the current ones
+    pub fn tightens(&self, max_per_tx: u64, max_per_window: u64) -> bool {
+        let within = |current: u64, new: u64| current == 0 || (new != 0 && new <= current);
+        within(self.max_per_tx, max_per_tx) && within(self.max_per_window, max_per_window)
+    }
+
+    /// Apply an outflow of `amount` to the rolling window, enforcing the caps
+    pub fn record_spend(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(
+            self.max_per_tx == 0 || amount <= self.max_per_tx,
+            ErrorCode::RateLimitExceeded
+        );
+
+        if now.saturating_sub(self.window_start) >= DEPOSIT_WINDOW_SECONDS {
+            self.window_start = now;
+            self.window_spent = 0;
+        }
+
+        let window_spent = self
+            .window_spent
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            self.max_per_window == 0 || window_spent <= self.max_per_window,
+            ErrorCode::RateLimitExceeded
+        );
+
+        self.window_spent = window_spent;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::Plan;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubscriptionStatus {
+    Active,                        // Paid up, charged every period
+    Lapsed,                        // Last charge failed, retried until the grace period ends
+    Inactive,                      // Grace period ran out without payment
+}
+
+#[account]
+pub struct Subscription {
+    pub user_pda: Pubkey,          // Deposit account that pays for the subscription
+    pub plan: Pubkey,              // Plan being billed
+    pub status: SubscriptionStatus, // Current billing status
+    pub next_charge_at: i64,       // When the next period becomes due
+    pub credit: u64,               // Prorated credit carried forward to future charges
+    pub last_charged_at: i64,      // When the last successful charge happened
+    pub created_at: i64,           // When the subscription was created
+    pub bump: u8,                  // PDA bump
+}
+
+impl Subscription {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 1; // discriminator + 2 pubkeys + status + credit + 3 timestamps + bump
+
+    /// Value of the paid but unused part of the current period, rounded down
+    pub fn prorated_credit(&self, plan: &Plan, now: i64) -> u64 {
+        if self.status != SubscriptionStatus::Active || now >= self.next_charge_at {
+            return 0;
+        }
+        let remaining = (self.next_charge_at - now).min(plan.period);
+        ((plan.price as u128) * (remaining as u128) / (plan.period as u128)) as u64
+    }
+}
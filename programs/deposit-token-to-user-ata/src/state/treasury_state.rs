@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
 
 #[account]
 pub struct TreasuryState {
@@ -6,8 +7,34 @@ pub struct TreasuryState {
     pub treasury_ata: Pubkey,   // The treasury's ATA address
     pub bump: u8,               // PDA bump
     pub created_at: i64,        // When treasury was created
+    pub min_deposit: u64,       // Smallest accepted deposit (0 = no minimum)
+    pub max_deposit: u64,       // Largest accepted deposit (0 = no maximum)
+    pub total_refunded: u64,    // Paid back to users with `refund_to_user`
+    pub version: u8,            // Layout version, see `ACCOUNT_VERSION`
+    pub reserved: [u8; 32],     // Room for future fields, zeroed
 }
 
 impl TreasuryState {
-    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 32; // discriminator + 2 pubkeys + bump + timestamp
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 1 + 32; // discriminator + 2 pubkeys + bump + timestamp + deposit limits + total refunded + version + reserved
+
+    /// Set the deposit bounds; 0 disables a bound
+    pub fn set_deposit_limits(&mut self, min_deposit: u64, max_deposit: u64) -> Result<()> {
+        require!(
+            max_deposit == 0 || min_deposit <= max_deposit,
+            ErrorCode::InvalidDepositLimits
+        );
+        self.min_deposit = min_deposit;
+        self.max_deposit = max_deposit;
+        Ok(())
+    }
+
+    /// Reject dust and fat-finger deposits for this mint
+    pub fn check_deposit_limits(&self, amount: u64) -> Result<()> {
+        require!(amount >= self.min_deposit, ErrorCode::DepositBelowMinimum);
+        require!(
+            self.max_deposit == 0 || amount <= self.max_deposit,
+            ErrorCode::DepositAboveMaximum
+        );
+        Ok(())
+    }
 }
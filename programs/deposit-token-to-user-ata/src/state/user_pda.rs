@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{Lock, TierLimits, DEPOSIT_WINDOW_SECONDS};
 
 // Data structures
 #[account]
@@ -8,15 +10,89 @@ pub struct UserPDA {
     pub token_account: Pubkey,     // The associated token account address
     pub bump: u8,                  // PDA bump seed
     pub created_at: i64,           // Timestamp when created
+    pub tier: u8,                  // Limit tier, index into `ProgramConfig::tier_limits`
+    pub window_start: i64,         // Start of the current deposit window
+    pub window_deposited: u64,     // Amount deposited in the current window
+    pub has_lock: bool,            // A `Lock` exists for this account
+    pub total_refunded: u64,       // Paid back from the treasury with `refund_to_user`
+    pub referrer: Option<Pubkey>,  // UserPDA of the user who referred this one
+    pub referred_volume: u64,      // Deposits credited to users this account referred
+    pub referral_rewards: u64,     // Referral rewards earned and not yet claimed
+    pub referral_rewards_claimed: u64, // Referral rewards paid out so far
+    pub frozen: bool,              // Set by the guardian or admin; blocks all movement of funds
+    pub version: u8,               // Layout version, see `ACCOUNT_VERSION`
+    pub reserved: [u8; 32],        // Room for future fields, zeroed
 }
 
 impl UserPDA {
     pub fn space(user_id: &str) -> usize {
+        Self::space_for_id_len(user_id.len())
+    }
+
+    /// Size of an account whose `user_id` is `id_len` bytes long
+    pub fn space_for_id_len(id_len: usize) -> usize {
         8 +                        // discriminator
-            4 + user_id.len() +        // string length + content
+            4 + id_len +               // string length + content
             32 +                       // owner pubkey
             32 +                       // token_account pubkey
             1 +                        // bump
-            8                          // created_at timestamp
+            8 +                        // created_at timestamp
+            1 +                        // tier
+            8 +                        // window_start
+            8 +                        // window_deposited
+            1 +                        // has_lock
+            8 +                        // total_refunded
+            1 + 32 +                   // referrer
+            8 +                        // referred_volume
+            8 +                        // referral_rewards
+            8 +                        // referral_rewards_claimed
+            1 +                        // frozen
+            1 +                        // version
+            32                         // reserved
+    }
+
+    /// Part of `balance` that is not held back by the user's lock.
+    /// Fails if the account has a lock but it was not passed in.
+    pub fn available_balance(&self, lock: Option<&Lock>, balance: u64, now: i64) -> Result<u64> {
+        if !self.has_lock {
+            return Ok(balance);
+        }
+        let lock = lock.ok_or(ErrorCode::MissingLockAccount)?;
+        Ok(balance.saturating_sub(lock.locked_amount(now)))
+    }
+
+    /// Apply a deposit to the rolling window, enforcing the tier limits.
+    /// `balance` is the deposit account balance before this deposit.
+    pub fn record_deposit(
+        &mut self,
+        limits: &TierLimits,
+        balance: u64,
+        amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        if now.saturating_sub(self.window_start) >= DEPOSIT_WINDOW_SECONDS {
+            self.window_start = now;
+            self.window_deposited = 0;
+        }
+
+        let window_deposited = self
+            .window_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            limits.daily_limit == 0 || window_deposited <= limits.daily_limit,
+            ErrorCode::DepositLimitExceeded
+        );
+
+        let new_balance = balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            limits.max_balance == 0 || new_balance <= limits.max_balance,
+            ErrorCode::DepositLimitExceeded
+        );
+
+        self.window_deposited = window_deposited;
+        Ok(())
     }
 }
\ No newline at end of file
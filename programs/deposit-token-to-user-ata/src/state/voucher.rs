@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Withdrawal voucher signed off-chain by `ProgramConfig::voucher_signer`.
+/// The ed25519-signed message is the Borsh serialization of this struct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Voucher {
+    pub program_id: Pubkey,        // Program the voucher is valid for
+    pub user_id: String,           // Deposit account to withdraw from
+    pub mint: Pubkey,              // Token being withdrawn
+    pub amount: u64,               // Amount to withdraw
+    pub destination: Pubkey,       // Token account that receives the funds
+    pub nonce: u64,                // Single-use voucher nonce
+    pub expiry: i64,               // Voucher cannot be redeemed after this time
+}
+
+#[account]
+pub struct VoucherRedemption {
+    pub user_pda: Pubkey,          // Deposit account the voucher drew on
+    pub destination: Pubkey,       // Where the funds went
+    pub amount: u64,               // Amount withdrawn
+    pub nonce: u64,                // Voucher nonce
+    pub redeemed_at: i64,          // When the voucher was redeemed
+    pub bump: u8,                  // PDA bump
+}
+
+impl VoucherRedemption {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1; // discriminator + 2 pubkeys + amount + nonce + timestamp + bump
+}
@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction, keccak};
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::errors::ErrorCode;
+use crate::events::SweepLeg;
+use crate::state::{Escrow, SpendingLimit, SweepSplit};
+
+/// Transfer tokens out of a program-owned token account, signing with the PDA seeds
+pub fn pda_transfer<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = anchor_spl::token::Transfer { from, to, authority };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    anchor_spl::token::transfer(cpi_ctx, amount)
+}
+
+/// Check that `account` is the associated token account of `owner` for `mint`
+pub fn check_ata(account: &AccountInfo, owner: &Pubkey, mint: &Pubkey) -> Result<()> {
+    require_keys_eq!(
+        account.key(),
+        get_associated_token_address(owner, mint),
+        ErrorCode::InvalidSplitDestination
+    );
+    require_keys_eq!(*account.owner, anchor_spl::token::ID, ErrorCode::InvalidSplitDestination);
+    Ok(())
+}
+
+/// Accounts shared by every leg of a sweep out of a user PDA ATA
+pub struct SweepAccounts<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub from: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub treasury_state: Pubkey,
+    pub treasury_ata: AccountInfo<'info>,
+    pub mint: Pubkey,
+}
+
+/// Pay out each sweep leg. Legs addressed to the treasury state go to the
+/// treasury ATA; every other leg takes the next ATA from `destinations`.
+pub fn transfer_sweep_legs<'info>(
+    accounts: &SweepAccounts<'info>,
+    legs: Vec<(SweepSplit, u64)>,
+    destinations: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<Vec<SweepLeg>> {
+    let mut destinations = destinations.iter();
+    let mut paid = Vec::with_capacity(legs.len());
+
+    for (split, amount) in legs {
+        let destination = if split.recipient == accounts.treasury_state {
+            accounts.treasury_ata.clone()
+        } else {
+            let info = destinations.next().ok_or(ErrorCode::InvalidAccountList)?;
+            check_ata(info, &split.recipient, &accounts.mint)?;
+            info.clone()
+        };
+
+        if amount > 0 {
+            pda_transfer(
+                accounts.token_program.clone(),
+                accounts.from.clone(),
+                destination.clone(),
+                accounts.authority.clone(),
+                signer_seeds,
+                amount,
+            )?;
+        }
+
+        paid.push(SweepLeg {
+            recipient: split.recipient,
+            destination: destination.key(),
+            bps: split.bps,
+            amount,
+        });
+    }
+
+    Ok(paid)
+}
+
+/// Pay the whole escrow balance out to `to`, signing as the escrow PDA
+pub fn settle_escrow<'info>(
+    token_program: AccountInfo<'info>,
+    escrow: &Account<'info, Escrow>,
+    escrow_ata: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+) -> Result<()> {
+    let escrow_id_bytes = escrow.escrow_id.to_le_bytes();
+    let seeds = &[
+        b"escrow",
+        escrow.buyer_pda.as_ref(),
+        escrow.seller_pda.as_ref(),
+        escrow_id_bytes.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    pda_transfer(token_program, escrow_ata, to, escrow.to_account_info(), signer_seeds, escrow.amount)
+}
+
+/// Size of the Ed25519 program's per-signature offsets record
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Check that `ix` is an Ed25519 program instruction verifying one signature
+/// by `signer` over exactly `message`, with all data held in `ix` itself.
+/// The runtime has already rejected the transaction if the signature is bad.
+pub fn verify_ed25519_ix(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::InvalidVoucherSignature);
+    require!(ix.accounts.is_empty(), ErrorCode::InvalidVoucherSignature);
+
+    let data = &ix.data;
+    require!(data.len() >= 2 + ED25519_OFFSETS_LEN, ErrorCode::InvalidVoucherSignature);
+    require!(data[0] == 1, ErrorCode::InvalidVoucherSignature); // exactly one signature
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+
+    // Every piece must come from this instruction, not another one in the transaction
+    require!(
+        signature_ix_index == u16::MAX && public_key_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        ErrorCode::InvalidVoucherSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidVoucherSignature)?;
+    require!(public_key == signer.as_ref(), ErrorCode::InvalidVoucherSignature);
+
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidVoucherSignature)?;
+    require!(signed_message == message, ErrorCode::InvalidVoucherSignature);
+
+    Ok(())
+}
+
+/// Leaf hash of a distribution entry
+pub fn distribution_leaf(index: u32, user_id: &str, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[
+        &index.to_le_bytes(),
+        user_id.as_bytes(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Verify a Merkle proof whose pairs are hashed in sorted order
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+/// Charge `amount` against the `SpendingLimit` PDA at `limit_info`.
+/// The caller checks the address; an uninitialized PDA means the key has no
+/// limit configured and the outflow is refused.
+pub fn charge_spending_limit(limit_info: &AccountInfo, amount: u64, now: i64) -> Result<()> {
+    require!(!limit_info.data_is_empty(), ErrorCode::SpendingLimitRequired);
+
+    require_keys_eq!(*limit_info.owner, crate::ID, ErrorCode::InvalidPDA);
+    let mut data = limit_info.try_borrow_mut_data()?;
+    let mut limit = SpendingLimit::try_deserialize(&mut &data[..])?;
+    limit.record_spend(amount, now)?;
+    limit.try_serialize(&mut &mut data[..])
+}
+
+/// Grow a legacy account to `new_len` in place. The new bytes are zeroed and
+/// `payer` tops up the rent; accounts already that large are left alone.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Create a program-owned PDA of `space` bytes at `account`, with `payer`
+/// funding the rent. Works like Anchor's `init`, including for an address
+/// that already holds lamports.
+pub fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
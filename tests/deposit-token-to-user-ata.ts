@@ -5,6 +5,8 @@ import {
   PublicKey,
   Keypair,
   SystemProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
@@ -13,7 +15,10 @@ import {
   createMint,
   mintTo,
   getAccount as getTokenAccount, createAssociatedTokenAccountInstruction,
+  createAccount,
+  approve,
 } from "@solana/spl-token";
+import { keccak_256 } from "@noble/hashes/sha3";
 import { expect } from "chai";
 
 // todo : for now added only success tes in this file. we need to make separate file
@@ -38,6 +43,24 @@ describe("deposit-token-to-user-ata", () => {
   // const DEVNET_CUSTOM_TOKEN_MINT = new PublicKey("BNDvNxhxhcRay8s5WK7wtpCwF9wGQiuUVo9zioGMtuS");
   const LOCALNET_CUSTOM_TOKEN_MINT = new PublicKey("HHmhD8ae2jaXY4QoRKQ2EBpXkaGEWHA8DYevePNNkLKy");
 
+  // `ProgramConfig` PDA
+  const configAddress = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+  )[0];
+
+  // `BlockedWallet` PDA for a depositor
+  const blockedWalletAddress = (wallet: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("blocked"), wallet.toBuffer()],
+      program.programId
+  )[0];
+
+  // `SpendingLimit` PDA for a sweeper key or treasury
+  const spendingLimitAddress = (key: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("spending_limit"), key.toBuffer()],
+      program.programId
+  )[0];
+
   before(async () => {
     // Create a payer keypair for tests
     payer = Keypair.generate();
@@ -137,11 +160,12 @@ describe("deposit-token-to-user-ata", () => {
       console.log(`   Mint: ${testMint.toString()}`);
 
       const result = await program.methods
-          .createUserDepositAccount(TEST_USER_ID)
+          .createUserDepositAccount(TEST_USER_ID, null)
           .accounts({
             userPda: expectedPda,
             userAta: expectedAta,
             subscriptionTokenMint: testMint,
+            referrerPda: null,
             payer: payer.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -189,11 +213,12 @@ describe("deposit-token-to-user-ata", () => {
 
       try {
         await program.methods
-            .createUserDepositAccount(TEST_USER_ID)
+            .createUserDepositAccount(TEST_USER_ID, null)
             .accounts({
               userPda: expectedPda,
               userAta: expectedAta,
               subscriptionTokenMint: testMint,
+              referrerPda: null,
               payer: payer.publicKey,
               tokenProgram: TOKEN_PROGRAM_ID,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -251,11 +276,12 @@ describe("deposit-token-to-user-ata", () => {
         );
 
         const result = await program.methods
-            .createUserDepositAccount(userId)
+            .createUserDepositAccount(userId, null)
             .accounts({
               userPda: expectedPda,
               userAta: expectedAta,
               subscriptionTokenMint: testMint,
+              referrerPda: null,
               payer: payer.publicKey,
               tokenProgram: TOKEN_PROGRAM_ID,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -315,11 +341,114 @@ describe("deposit-token-to-user-ata", () => {
     });
   });
 
+  describe("Program Initialize Function", () => {
+    it("Should initialize the program with admin and treasury", async () => {
+      // Calculate expected PDA addresses
+      const [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+
+      const [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+
+      const treasuryAtaAddress = getAssociatedTokenAddressSync(
+          testMint,
+          treasuryStateAddress,
+          true
+      );
+
+      console.log(`\n🔧 Initializing program:`);
+      console.log(`   Token Mint: ${testMint.toString()}`);
+      console.log(`   Admin State PDA: ${adminStateAddress.toString()}`);
+      console.log(`   Treasury State PDA: ${treasuryStateAddress.toString()}`);
+      console.log(`   Treasury ATA: ${treasuryAtaAddress.toString()}`);
+
+      const tx = await program.methods
+          .initialize(testMint)
+          .accounts({
+            adminState: adminStateAddress,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            tokenMint: testMint,
+            payer: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      console.log("✅ Initialize transaction:", tx);
+
+      // Verify admin state
+      const adminState = await program.account.adminState.fetch(adminStateAddress);
+
+      expect(adminState.admin.toString()).to.equal(payer.publicKey.toString());
+      expect(adminState.createdAt.toNumber()).to.be.greaterThan(0);
+
+      // Verify treasury state
+      const treasuryState = await program.account.treasuryState.fetch(treasuryStateAddress);
+      console.log("treasury state == ", treasuryState)
+      expect(treasuryState.tokenMint.toString()).to.equal(testMint.toString());
+      expect(treasuryState.treasuryAta.toString()).to.equal(treasuryAtaAddress.toString());
+      expect(treasuryState.createdAt.toNumber()).to.be.greaterThan(0);
+
+      // Verify treasury ATA was created
+      const treasuryAtaAccount = await getTokenAccount(connection, treasuryAtaAddress);
+      expect(treasuryAtaAccount.owner.toString()).to.equal(treasuryStateAddress.toString());
+      expect(treasuryAtaAccount.mint.toString()).to.equal(testMint.toString());
+
+      console.log(`✅ Program initialization verified successfully`);
+    });
+
+    it("Should initialize the program config with limits disabled", async () => {
+      const [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      const [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+
+      await program.methods
+          .initializeConfig()
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: programConfigAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const programConfig = await program.account.programConfig.fetch(programConfigAddress);
+      for (const limits of programConfig.tierLimits) {
+        expect(limits.maxBalance.toNumber()).to.equal(0);
+        expect(limits.dailyLimit.toNumber()).to.equal(0);
+      }
+    });
+  });
+
   describe("Token Deposit Tests", () => {
     let userTokenAccount: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let programConfigAddress: PublicKey;
     const depositAmount = 500 * Math.pow(10, 9); // 500 tokens with 9 decimals
 
     before(async () => {
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+
       // Create user's personal token account for depositing from
       userTokenAccount = getAssociatedTokenAddressSync(
           testMint,
@@ -369,14 +498,25 @@ describe("deposit-token-to-user-ata", () => {
       console.log(`   PDA balance before: ${Number(pdaBalanceBefore.amount) / Math.pow(10, 9)}`);
 
       const tx = await program.methods
-          .depositTokens(TEST_USER_ID, new anchor.BN(depositAmount))
+          .depositTokens(TEST_USER_ID, new anchor.BN(depositAmount), null, null, null, null)
           .accounts({
             userPda: userPdaAddress,
             userPdaAta: userAtaAddress,
             userTokenAccount: userTokenAccount,
             mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
             user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
           })
           .signers([payer])
           .rpc();
@@ -400,14 +540,25 @@ describe("deposit-token-to-user-ata", () => {
 
       try {
         await program.methods
-            .depositTokens(TEST_USER_ID, new anchor.BN(excessiveAmount))
+            .depositTokens(TEST_USER_ID, new anchor.BN(excessiveAmount), null, null, null, null)
             .accounts({
               userPda: userPdaAddress,
               userPdaAta: userAtaAddress,
               userTokenAccount: userTokenAccount,
               mint: testMint,
+              treasuryState: treasuryStateAddress,
+              programConfig: programConfigAddress,
+              feeVault: null,
               user: payer.publicKey,
+              blockedWallet: blockedWalletAddress(payer.publicKey),
+              attestation: null,
               tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              referenceReceipt: null,
+              depositReceipt: null,
+              lock: null,
+              adminState: null,
+              referrerPda: null,
             })
             .signers([payer])
             .rpc();
@@ -422,14 +573,25 @@ describe("deposit-token-to-user-ata", () => {
     it("Should fail deposit with zero amount", async () => {
       try {
         await program.methods
-            .depositTokens(TEST_USER_ID, new anchor.BN(0))
+            .depositTokens(TEST_USER_ID, new anchor.BN(0), null, null, null, null)
             .accounts({
               userPda: userPdaAddress,
               userPdaAta: userAtaAddress,
               userTokenAccount: userTokenAccount,
               mint: testMint,
+              treasuryState: treasuryStateAddress,
+              programConfig: programConfigAddress,
+              feeVault: null,
               user: payer.publicKey,
+              blockedWallet: blockedWalletAddress(payer.publicKey),
+              attestation: null,
               tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              referenceReceipt: null,
+              depositReceipt: null,
+              lock: null,
+              adminState: null,
+              referrerPda: null,
             })
             .signers([payer])
             .rpc();
@@ -452,11 +614,12 @@ describe("deposit-token-to-user-ata", () => {
 
       // Create the other user's account first
       await program.methods
-          .createUserDepositAccount(anotherUserId)
+          .createUserDepositAccount(anotherUserId, null)
           .accounts({
             userPda: anotherPda,
             userAta: anotherAta,
             subscriptionTokenMint: testMint,
+            referrerPda: null,
             payer: payer.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -469,14 +632,25 @@ describe("deposit-token-to-user-ata", () => {
 
       // Deposit to another user's ATA
       const tx = await program.methods
-          .depositTokens(anotherUserId, new anchor.BN(depositToAnotherAmount))
+          .depositTokens(anotherUserId, new anchor.BN(depositToAnotherAmount), null, null, null, null)
           .accounts({
             userPda: anotherPda,
             userPdaAta: anotherAta,
             userTokenAccount: userTokenAccount,
             mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
             user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
           })
           .signers([payer])
           .rpc();
@@ -498,28 +672,50 @@ describe("deposit-token-to-user-ata", () => {
 
       // First deposit
       await program.methods
-          .depositTokens(TEST_USER_ID, new anchor.BN(firstDeposit))
+          .depositTokens(TEST_USER_ID, new anchor.BN(firstDeposit), null, null, null, null)
           .accounts({
             userPda: userPdaAddress,
             userPdaAta: userAtaAddress,
             userTokenAccount: userTokenAccount,
             mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
             user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
           })
           .signers([payer])
           .rpc();
 
       // Second deposit
       await program.methods
-          .depositTokens(TEST_USER_ID, new anchor.BN(secondDeposit))
+          .depositTokens(TEST_USER_ID, new anchor.BN(secondDeposit), null, null, null, null)
           .accounts({
             userPda: userPdaAddress,
             userPdaAta: userAtaAddress,
             userTokenAccount: userTokenAccount,
             mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
             user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
           })
           .signers([payer])
           .rpc();
@@ -535,142 +731,2747 @@ describe("deposit-token-to-user-ata", () => {
 
       expect(Number(finalBalance.amount)).to.equal(expectedAmount);
     });
-  });
-
-  describe("Program Initialize Function", () => {
-    it("Should initialize the program with admin and treasury", async () => {
-      // Calculate expected PDA addresses
-      const [adminStateAddress] = PublicKey.findProgramAddressSync(
-          [Buffer.from("admin")],
-          program.programId
-      );
 
-      const [treasuryStateAddress] = PublicKey.findProgramAddressSync(
-          [Buffer.from("treasury"), testMint.toBuffer()],
-          program.programId
-      );
+    it("Should deposit from a non-ATA token account owned by the depositor", async () => {
+      const vaultAmount = 40 * Math.pow(10, 9);
 
-      const treasuryAtaAddress = getAssociatedTokenAddressSync(
+      // Plain token account (not an ATA) owned by the payer
+      const vaultAccount = await createAccount(
+          connection,
+          payer,
           testMint,
-          treasuryStateAddress,
-          true
+          payer.publicKey,
+          Keypair.generate()
       );
+      await mintTo(connection, payer, testMint, vaultAccount, payer.publicKey, vaultAmount);
 
-      console.log(`\n🔧 Initializing program:`);
-      console.log(`   Token Mint: ${testMint.toString()}`);
-      console.log(`   Admin State PDA: ${adminStateAddress.toString()}`);
-      console.log(`   Treasury State PDA: ${treasuryStateAddress.toString()}`);
-      console.log(`   Treasury ATA: ${treasuryAtaAddress.toString()}`);
+      const pdaBalanceBefore = await getTokenAccount(connection, userAtaAddress);
 
-      const tx = await program.methods
-          .initialize(testMint)
+      await program.methods
+          .depositTokens(TEST_USER_ID, new anchor.BN(vaultAmount), null, null, null, null)
           .accounts({
-            adminState: adminStateAddress,
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            userTokenAccount: vaultAccount,
+            mint: testMint,
             treasuryState: treasuryStateAddress,
-            treasuryAta: treasuryAtaAddress,
-            tokenMint: testMint,
-            payer: payer.publicKey,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
             tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
           })
           .signers([payer])
           .rpc();
 
-      console.log("✅ Initialize transaction:", tx);
-
-      // Verify admin state
-      const adminState = await program.account.adminState.fetch(adminStateAddress);
-
-      expect(adminState.admin.toString()).to.equal(payer.publicKey.toString());
-      expect(adminState.createdAt.toNumber()).to.be.greaterThan(0);
+      const pdaBalanceAfter = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(pdaBalanceAfter.amount)).to.equal(Number(pdaBalanceBefore.amount) + vaultAmount);
+    });
 
-      // Verify treasury state
-      const treasuryState = await program.account.treasuryState.fetch(treasuryStateAddress);
-      console.log("treasury state == ", treasuryState)
-      expect(treasuryState.tokenMint.toString()).to.equal(testMint.toString());
-      expect(treasuryState.treasuryAta.toString()).to.equal(treasuryAtaAddress.toString());
-      expect(treasuryState.createdAt.toNumber()).to.be.greaterThan(0);
+    it("Should deposit as an approved delegate and reject amounts above the allowance", async () => {
+      const allowance = 30 * Math.pow(10, 9);
+      const delegate = Keypair.generate();
+      const airdropTx = await connection.requestAirdrop(delegate.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdropTx);
 
-      // Verify treasury ATA was created
-      const treasuryAtaAccount = await getTokenAccount(connection, treasuryAtaAddress);
-      expect(treasuryAtaAccount.owner.toString()).to.equal(treasuryStateAddress.toString());
-      expect(treasuryAtaAccount.mint.toString()).to.equal(testMint.toString());
+      await approve(connection, payer, userTokenAccount, delegate.publicKey, payer, allowance);
 
-      console.log(`✅ Program initialization verified successfully`);
-    });
-  });
+      try {
+        await program.methods
+            .depositTokens(TEST_USER_ID, new anchor.BN(allowance + 1), null, null, null, null)
+            .accounts({
+              userPda: userPdaAddress,
+              userPdaAta: userAtaAddress,
+              userTokenAccount: userTokenAccount,
+              mint: testMint,
+              treasuryState: treasuryStateAddress,
+              programConfig: programConfigAddress,
+              feeVault: null,
+              user: delegate.publicKey,
+              blockedWallet: blockedWalletAddress(delegate.publicKey),
+              attestation: null,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              referenceReceipt: null,
+              depositReceipt: null,
+              lock: null,
+              adminState: null,
+              referrerPda: null,
+            })
+            .signers([delegate])
+            .rpc();
 
-  describe("Admin Transfer to Treasury", () => {
-    let adminStateAddress: PublicKey;
-    let treasuryStateAddress: PublicKey;
-    let treasuryAtaAddress: PublicKey;
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("InsufficientDelegatedAmount");
+      }
 
-    before(async () => {
-      // Calculate PDA addresses (these should exist from previous initialization)
-      [adminStateAddress] = PublicKey.findProgramAddressSync(
-          [Buffer.from("admin")],
-          program.programId
-      );
+      const pdaBalanceBefore = await getTokenAccount(connection, userAtaAddress);
 
-      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
-          [Buffer.from("treasury"), testMint.toBuffer()],
-          program.programId
-      );
+      await program.methods
+          .depositTokens(TEST_USER_ID, new anchor.BN(allowance), null, null, null, null)
+          .accounts({
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            userTokenAccount: userTokenAccount,
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: delegate.publicKey,
+            blockedWallet: blockedWalletAddress(delegate.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
+          })
+          .signers([delegate])
+          .rpc();
 
-      treasuryAtaAddress = getAssociatedTokenAddressSync(
-          testMint,
-          treasuryStateAddress,
-          true
-      );
+      const pdaBalanceAfter = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(pdaBalanceAfter.amount)).to.equal(Number(pdaBalanceBefore.amount) + allowance);
     });
 
-    it("Should transfer tokens from user PDA to treasury", async () => {
-      const transferAmount = 200 * Math.pow(10, 9); // 200 tokens
+    it("Should reject a source account the depositor neither owns nor is delegated on", async () => {
+      const stranger = Keypair.generate();
+      const airdropTx = await connection.requestAirdrop(stranger.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdropTx);
 
-      // Get initial balances
-      const userAtaBalanceBefore = await getTokenAccount(connection, userAtaAddress);
+      try {
+        await program.methods
+            .depositTokens(TEST_USER_ID, new anchor.BN(1), null, null, null, null)
+            .accounts({
+              userPda: userPdaAddress,
+              userPdaAta: userAtaAddress,
+              userTokenAccount: userTokenAccount,
+              mint: testMint,
+              treasuryState: treasuryStateAddress,
+              programConfig: programConfigAddress,
+              feeVault: null,
+              user: stranger.publicKey,
+              blockedWallet: blockedWalletAddress(stranger.publicKey),
+              attestation: null,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              referenceReceipt: null,
+              depositReceipt: null,
+              lock: null,
+              adminState: null,
+              referrerPda: null,
+            })
+            .signers([stranger])
+            .rpc();
 
-      // console.log("userAtaBalanceBefore == ", userAtaBalanceBefore)
-      const treasuryAtaBalanceBefore = await getTokenAccount(connection, treasuryAtaAddress);
-      // console.log("treasuryAtaBalanceBefore == ", treasuryAtaBalanceBefore)
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedDepositSource");
+      }
+    });
 
-      console.log(`\n💰 Admin transferring to treasury:`);
-      console.log(`   Amount: ${transferAmount / Math.pow(10, 9)} tokens`);
-      console.log(`   From user ATA: ${userAtaAddress.toString()}`);
-      console.log(`   To treasury ATA: ${treasuryAtaAddress.toString()}`);
-      console.log(`   User balance before: ${Number(userAtaBalanceBefore.amount) / Math.pow(10, 9)}`);
-      console.log(`   Treasury balance before: ${Number(treasuryAtaBalanceBefore.amount) / Math.pow(10, 9)}`);
+    it("Should record a deposit reference and reject its reuse", async () => {
+      const reference = Array.from(Buffer.alloc(32, 7));
+      const [referenceReceipt] = PublicKey.findProgramAddressSync(
+          [Buffer.from("reference"), userPdaAddress.toBuffer(), Buffer.from(reference)],
+          program.programId
+      );
 
-      const tx = await program.methods
-          .adminTransferToTreasury(TEST_USER_ID, new anchor.BN(transferAmount))
+      const depositWithReference = () => program.methods
+          .depositTokens(TEST_USER_ID, new anchor.BN(Math.pow(10, 9)), reference, "INV-2024-0001", null, null)
           .accounts({
-            adminState: adminStateAddress,
-            treasuryState: treasuryStateAddress,
             userPda: userPdaAddress,
             userPdaAta: userAtaAddress,
-            treasuryAta: treasuryAtaAddress,
+            userTokenAccount: userTokenAccount,
             mint: testMint,
-            admin: payer.publicKey,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
           })
           .signers([payer])
           .rpc();
 
-      console.log("✅ Admin transfer transaction:", tx);
+      await depositWithReference();
 
-      // Verify balances after transfer
-      const userAtaBalanceAfter = await getTokenAccount(connection, userAtaAddress);
-      const treasuryAtaBalanceAfter = await getTokenAccount(connection, treasuryAtaAddress);
+      const receipt = await program.account.referenceReceipt.fetch(referenceReceipt);
+      expect(receipt.userPda.toString()).to.equal(userPdaAddress.toString());
+      expect(receipt.reference).to.deep.equal(reference);
+      expect(receipt.amount.toNumber()).to.equal(Math.pow(10, 9));
 
-      console.log(`   User balance after: ${Number(userAtaBalanceAfter.amount) / Math.pow(10, 9)}`);
+      try {
+        await depositWithReference();
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("DuplicateReference");
+      }
+
+      // Reference receipts are permanent, unlike nonce receipts
+      try {
+        await program.methods
+            .closeDepositReceipt()
+            .accounts({
+              depositReceipt: referenceReceipt,
+              depositor: payer.publicKey,
+            })
+            .signers([payer])
+            .rpc();
+        expect.fail("Reference receipt should not be closable");
+      } catch (error) {
+        expect(error.message).to.include("AccountDiscriminatorMismatch");
+      }
+    });
+
+    it("Should reject a retried deposit with the same nonce", async () => {
+      const nonce = new anchor.BN(42);
+      const [depositReceipt] = PublicKey.findProgramAddressSync(
+          [Buffer.from("receipt"), userPdaAddress.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+          program.programId
+      );
+
+      const depositWithNonce = () => program.methods
+          .depositTokens(TEST_USER_ID, new anchor.BN(Math.pow(10, 9)), null, null, nonce, null)
+          .accounts({
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            userTokenAccount: userTokenAccount,
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
+          })
+          .signers([payer])
+          .rpc();
+
+      await depositWithNonce();
+      const balanceAfterFirst = await getTokenAccount(connection, userAtaAddress);
+
+      try {
+        await depositWithNonce();
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("DuplicateDeposit");
+      }
+
+      const balanceAfterRetry = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(balanceAfterRetry.amount)).to.equal(Number(balanceAfterFirst.amount));
+
+      // Receipt is still inside its retention window
+      try {
+        await program.methods
+            .closeDepositReceipt()
+            .accounts({ depositReceipt, depositor: payer.publicKey })
+            .signers([payer])
+            .rpc();
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("ReceiptRetentionActive");
+      }
+    });
+
+    it("Should enforce the per-mint minimum and maximum deposit", async () => {
+      const [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      const setLimits = (min: number, max: number) => program.methods
+          .updateDepositLimits(new anchor.BN(min), new anchor.BN(max))
+          .accounts({
+            adminState: adminStateAddress,
+            treasuryState: treasuryStateAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+      const deposit = (amount: number) => program.methods
+          .depositTokens(TEST_USER_ID, new anchor.BN(amount), null, null, null, null)
+          .accounts({
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            userTokenAccount: userTokenAccount,
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
+          })
+          .signers([payer])
+          .rpc();
+
+      await setLimits(2 * Math.pow(10, 9), 5 * Math.pow(10, 9));
+
+      try {
+        await deposit(Math.pow(10, 9));
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("DepositBelowMinimum");
+      }
+
+      try {
+        await deposit(6 * Math.pow(10, 9));
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("DepositAboveMaximum");
+      }
+
+      await deposit(3 * Math.pow(10, 9));
+
+      // Remove the limits again for the remaining tests
+      await setLimits(0, 0);
+    });
+
+    it("Should enforce the rolling daily limit of the user's tier", async () => {
+      const tierUserId = "TierUser";
+      const [tierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("deposit"), Buffer.from(tierUserId)],
+          program.programId
+      );
+      const tierAta = getAssociatedTokenAddressSync(testMint, tierPda, true);
+      const [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+
+      await program.methods
+          .createUserDepositAccount(tierUserId, null)
+          .accounts({
+            userPda: tierPda,
+            userAta: tierAta,
+            subscriptionTokenMint: testMint,
+            referrerPda: null,
+            payer: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      // Tier 1: at most 10 tokens per day
+      await program.methods
+          .updateTierLimits(1, new anchor.BN(0), new anchor.BN(10 * Math.pow(10, 9)))
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: programConfigAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+      await program.methods
+          .setUserTier(tierUserId, 1)
+          .accounts({
+            adminState: adminStateAddress,
+            userPda: tierPda,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      const deposit = (amount: number) => program.methods
+          .depositTokens(tierUserId, new anchor.BN(amount), null, null, null, null)
+          .accounts({
+            userPda: tierPda,
+            userPdaAta: tierAta,
+            userTokenAccount: userTokenAccount,
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
+          })
+          .signers([payer])
+          .rpc();
+
+      await deposit(6 * Math.pow(10, 9));
+
+      try {
+        await deposit(5 * Math.pow(10, 9));
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("DepositLimitExceeded");
+      }
+
+      const tierPdaAccount = await program.account.userPda.fetch(tierPda);
+      expect(tierPdaAccount.tier).to.equal(1);
+      expect(tierPdaAccount.windowDeposited.toNumber()).to.equal(6 * Math.pow(10, 9));
+    });
+
+    it("Should leave an up-to-date user account unchanged when migrating", async () => {
+      const [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      const before = await connection.getAccountInfo(userPdaAddress);
+
+      await program.methods
+          .migrateUserPda(TEST_USER_ID)
+          .accounts({
+            adminState: adminStateAddress,
+            userPda: userPdaAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const after = await connection.getAccountInfo(userPdaAddress);
+      expect(after.data.length).to.equal(before.data.length);
+      expect(after.lamports).to.equal(before.lamports);
+    });
+
+    it("Should route the deposit fee to the fee vault", async () => {
+      const [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      const feeRecipient = Keypair.generate();
+      const feeVault = getAssociatedTokenAddressSync(testMint, feeRecipient.publicKey);
+      await anchor.web3.sendAndConfirmTransaction(
+          connection,
+          new anchor.web3.Transaction().add(
+              createAssociatedTokenAccountInstruction(payer.publicKey, feeVault, feeRecipient.publicKey, testMint)
+          ),
+          [payer]
+      );
+
+      const setFee = (feeBps: number) => program.methods
+          .updateFeeConfig(feeBps, feeRecipient.publicKey)
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: programConfigAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      // 1% fee
+      await setFee(100);
+
+      const depositAmount = 10 * Math.pow(10, 9);
+      const pdaBalanceBefore = await getTokenAccount(connection, userAtaAddress);
+
+      await program.methods
+          .depositTokens(TEST_USER_ID, new anchor.BN(depositAmount), null, null, null, null)
+          .accounts({
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            userTokenAccount: userTokenAccount,
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: null,
+          })
+          .signers([payer])
+          .rpc();
+
+      const pdaBalanceAfter = await getTokenAccount(connection, userAtaAddress);
+      const feeVaultBalance = await getTokenAccount(connection, feeVault);
+      expect(Number(feeVaultBalance.amount)).to.equal(depositAmount / 100);
+      expect(Number(pdaBalanceAfter.amount)).to.equal(Number(pdaBalanceBefore.amount) + depositAmount - depositAmount / 100);
+
+      // Fees above the hard-coded ceiling are rejected
+      try {
+        await setFee(501);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("FeeTooHigh");
+      }
+
+      await setFee(0);
+    });
+  });
+
+  describe("Admin Transfer to Treasury", () => {
+    let programConfigAddress: PublicKey;
+    let adminStateAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let treasuryAtaAddress: PublicKey;
+
+    before(async () => {
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+
+      // Calculate PDA addresses (these should exist from previous initialization)
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+
+      treasuryAtaAddress = getAssociatedTokenAddressSync(
+          testMint,
+          treasuryStateAddress,
+          true
+      );
+
+      // Outflows need a limit account; start both the sweeper and the treasury uncapped
+      for (const key of [payer.publicKey, treasuryStateAddress]) {
+        await program.methods
+            .setSpendingLimit(key, new anchor.BN(0), new anchor.BN(0))
+            .accounts({
+              adminState: adminStateAddress,
+              spendingLimit: spendingLimitAddress(key),
+              admin: payer.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([payer])
+            .rpc();
+      }
+    });
+
+    it("Should transfer tokens from user PDA to treasury", async () => {
+      const transferAmount = 200 * Math.pow(10, 9); // 200 tokens
+
+      // Get initial balances
+      const userAtaBalanceBefore = await getTokenAccount(connection, userAtaAddress);
+
+      // console.log("userAtaBalanceBefore == ", userAtaBalanceBefore)
+      const treasuryAtaBalanceBefore = await getTokenAccount(connection, treasuryAtaAddress);
+      // console.log("treasuryAtaBalanceBefore == ", treasuryAtaBalanceBefore)
+
+      console.log(`\n💰 Admin transferring to treasury:`);
+      console.log(`   Amount: ${transferAmount / Math.pow(10, 9)} tokens`);
+      console.log(`   From user ATA: ${userAtaAddress.toString()}`);
+      console.log(`   To treasury ATA: ${treasuryAtaAddress.toString()}`);
+      console.log(`   User balance before: ${Number(userAtaBalanceBefore.amount) / Math.pow(10, 9)}`);
+      console.log(`   Treasury balance before: ${Number(treasuryAtaBalanceBefore.amount) / Math.pow(10, 9)}`);
+
+      const tx = await program.methods
+          .adminTransferToTreasury(TEST_USER_ID, new anchor.BN(transferAmount))
+          .accounts({
+            adminState: adminStateAddress,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            userPda: userPdaAddress,
+            lock: null,
+            userPdaAta: userAtaAddress,
+            treasuryAta: treasuryAtaAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            spendingLimit: spendingLimitAddress(payer.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      console.log("✅ Admin transfer transaction:", tx);
+
+      // Verify balances after transfer
+      const userAtaBalanceAfter = await getTokenAccount(connection, userAtaAddress);
+      const treasuryAtaBalanceAfter = await getTokenAccount(connection, treasuryAtaAddress);
+
+      console.log(`   User balance after: ${Number(userAtaBalanceAfter.amount) / Math.pow(10, 9)}`);
       console.log(`   Treasury balance after: ${Number(treasuryAtaBalanceAfter.amount) / Math.pow(10, 9)}`);
 
-      // Assertions
-      expect(Number(userAtaBalanceAfter.amount)).to.equal(Number(userAtaBalanceBefore.amount) - transferAmount);
-      expect(Number(treasuryAtaBalanceAfter.amount)).to.equal(Number(treasuryAtaBalanceBefore.amount) + transferAmount);
+      // Assertions
+      expect(Number(userAtaBalanceAfter.amount)).to.equal(Number(userAtaBalanceBefore.amount) - transferAmount);
+      expect(Number(treasuryAtaBalanceAfter.amount)).to.equal(Number(treasuryAtaBalanceBefore.amount) + transferAmount);
+
+      console.log("✅ Admin transfer to treasury completed successfully");
+    });
+
+    it("Should split a sweep between the operations wallet and the treasury", async () => {
+      const opsWallet = Keypair.generate();
+      const opsAta = getAssociatedTokenAddressSync(testMint, opsWallet.publicKey);
+      await anchor.web3.sendAndConfirmTransaction(
+          connection,
+          new anchor.web3.Transaction().add(
+              createAssociatedTokenAccountInstruction(payer.publicKey, opsAta, opsWallet.publicKey, testMint)
+          ),
+          [payer]
+      );
+
+      const setSplits = (splits: { recipient: PublicKey; bps: number }[]) => program.methods
+          .updateSweepSplits(splits)
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: programConfigAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      // Weights must sum to 10,000 bps
+      try {
+        await setSplits([{ recipient: opsWallet.publicKey, bps: 2000 }]);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSplitConfig");
+      }
+
+      await setSplits([
+        { recipient: opsWallet.publicKey, bps: 2000 },
+        { recipient: treasuryStateAddress, bps: 8000 },
+      ]);
+
+      const sweepAmount = 10 * Math.pow(10, 9);
+      const treasuryBefore = await getTokenAccount(connection, treasuryAtaAddress);
+
+      await program.methods
+          .adminTransferToTreasury(TEST_USER_ID, new anchor.BN(sweepAmount))
+          .accounts({
+            adminState: adminStateAddress,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            userPda: userPdaAddress,
+            lock: null,
+            userPdaAta: userAtaAddress,
+            treasuryAta: treasuryAtaAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            spendingLimit: spendingLimitAddress(payer.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([{ pubkey: opsAta, isWritable: true, isSigner: false }])
+          .signers([payer])
+          .rpc();
+
+      const opsBalance = await getTokenAccount(connection, opsAta);
+      const treasuryAfter = await getTokenAccount(connection, treasuryAtaAddress);
+      expect(Number(opsBalance.amount)).to.equal(sweepAmount * 0.2);
+      expect(Number(treasuryAfter.amount)).to.equal(Number(treasuryBefore.amount) + sweepAmount * 0.8);
+
+      // Back to sweeping everything into the treasury
+      await setSplits([]);
+    });
+
+    it("Should keep locked deposits out of sweeps until they unlock", async () => {
+      const lockUserId = "LockUser";
+      const [lockPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("deposit"), Buffer.from(lockUserId)],
+          program.programId
+      );
+      const lockAta = getAssociatedTokenAddressSync(testMint, lockPda, true);
+      const [lockAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("lock"), lockPda.toBuffer()],
+          program.programId
+      );
+      const payerTokenAccount = getAssociatedTokenAddressSync(testMint, payer.publicKey, false);
+
+      await program.methods
+          .createUserDepositAccount(lockUserId, null)
+          .accounts({
+            userPda: lockPda,
+            userAta: lockAta,
+            subscriptionTokenMint: testMint,
+            referrerPda: null,
+            payer: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const deposit = (amount: number, lockSchedule, lock: PublicKey | null) => program.methods
+          .depositTokens(lockUserId, new anchor.BN(amount), null, null, null, lockSchedule)
+          .accounts({
+            userPda: lockPda,
+            userPdaAta: lockAta,
+            userTokenAccount: payerTokenAccount,
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: lock,
+            adminState: null,
+            referrerPda: null,
+          })
+          .signers([payer])
+          .rpc();
+
+      // 20 promotional tokens locked for an hour, 10 regular tokens
+      const unlockAt = Math.floor(Date.now() / 1000) + 3600;
+      await deposit(20 * Math.pow(10, 9), { unlockAt: new anchor.BN(unlockAt), vestingStart: new anchor.BN(0) }, lockAddress);
+      await deposit(10 * Math.pow(10, 9), null, null);
+
+      const info = await program.methods
+          .getUserBalanceInfo()
+          .accounts({
+            userPda: lockPda,
+            userPdaAta: lockAta,
+            lock: lockAddress,
+          })
+          .view();
+      expect(info.total.toNumber()).to.equal(30 * Math.pow(10, 9));
+      expect(info.locked.toNumber()).to.equal(20 * Math.pow(10, 9));
+      expect(info.available.toNumber()).to.equal(10 * Math.pow(10, 9));
+
+      const sweep = (amount: number, lock: PublicKey | null) => program.methods
+          .adminTransferToTreasury(lockUserId, new anchor.BN(amount))
+          .accounts({
+            adminState: adminStateAddress,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            userPda: lockPda,
+            lock: lock,
+            userPdaAta: lockAta,
+            treasuryAta: treasuryAtaAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            spendingLimit: spendingLimitAddress(payer.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      // Leaving out the lock does not get around it
+      try {
+        await sweep(Math.pow(10, 9), null);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("MissingLockAccount");
+      }
+
+      try {
+        await sweep(11 * Math.pow(10, 9), lockAddress);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("FundsLocked");
+      }
+
+      await sweep(10 * Math.pow(10, 9), lockAddress);
+      const lockAtaBalance = await getTokenAccount(connection, lockAta);
+      expect(Number(lockAtaBalance.amount)).to.equal(20 * Math.pow(10, 9));
+    });
+
+    it("Should keep a cliff lock cliff-shaped and only let the owner or admin lock", async () => {
+      const lockUserId = "LockUser";
+      const [lockPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("deposit"), Buffer.from(lockUserId)],
+          program.programId
+      );
+      const lockAta = getAssociatedTokenAddressSync(testMint, lockPda, true);
+      const [lockAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("lock"), lockPda.toBuffer()],
+          program.programId
+      );
+
+      const deposit = (depositor: Keypair, source: PublicKey, amount: number, lockSchedule) => program.methods
+          .depositTokens(lockUserId, new anchor.BN(amount), null, null, null, lockSchedule)
+          .accounts({
+            userPda: lockPda,
+            userPdaAta: lockAta,
+            userTokenAccount: source,
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: depositor.publicKey,
+            blockedWallet: blockedWalletAddress(depositor.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: lockAddress,
+            adminState: null,
+            referrerPda: null,
+          })
+          .signers([depositor])
+          .rpc();
+
+      // Add a vesting schedule on top of the existing one-hour cliff
+      const before = await program.account.lock.fetch(lockAddress);
+      expect(before.vestingStart.toNumber()).to.equal(0);
+      const now = Math.floor(Date.now() / 1000);
+      await deposit(
+          payer,
+          getAssociatedTokenAddressSync(testMint, payer.publicKey, false),
+          5 * Math.pow(10, 9),
+          { unlockAt: new anchor.BN(now + 7200), vestingStart: new anchor.BN(now) },
+      );
+
+      const after = await program.account.lock.fetch(lockAddress);
+      expect(after.vestingStart.toNumber()).to.equal(0);
+      expect(after.unlockAt.toNumber()).to.equal(now + 7200);
+      expect(after.amount.toNumber()).to.equal(25 * Math.pow(10, 9));
+
+      const info = await program.methods
+          .getUserBalanceInfo()
+          .accounts({
+            userPda: lockPda,
+            userPdaAta: lockAta,
+            lock: lockAddress,
+          })
+          .view();
+      expect(info.locked.toNumber()).to.equal(25 * Math.pow(10, 9));
+
+      // A third party cannot lock the user's balance
+      const stranger = Keypair.generate();
+      const airdropTx = await connection.requestAirdrop(stranger.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdropTx);
+      const strangerAccount = await createAccount(connection, payer, testMint, stranger.publicKey);
+      await mintTo(connection, payer, testMint, strangerAccount, payer.publicKey, Math.pow(10, 9));
+
+      try {
+        await deposit(
+            stranger,
+            strangerAccount,
+            Math.pow(10, 9),
+            { unlockAt: new anchor.BN(now + 365 * 24 * 3600), vestingStart: new anchor.BN(0) },
+        );
+        expect.fail("Lock from a third party should fail");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedLock");
+      }
+    });
+
+    it("Should cap sweeps with the sweeper's spending limit", async () => {
+      const setLimit = (maxPerTx: number, maxPerWindow: number) => program.methods
+          .setSpendingLimit(payer.publicKey, new anchor.BN(maxPerTx), new anchor.BN(maxPerWindow))
+          .accounts({
+            adminState: adminStateAddress,
+            spendingLimit: spendingLimitAddress(payer.publicKey),
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const sweep = (amount: number) => program.methods
+          .adminTransferToTreasury(TEST_USER_ID, new anchor.BN(amount))
+          .accounts({
+            adminState: adminStateAddress,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            userPda: userPdaAddress,
+            lock: null,
+            userPdaAta: userAtaAddress,
+            treasuryAta: treasuryAtaAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            spendingLimit: spendingLimitAddress(payer.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      const token = Math.pow(10, 9);
+      await setLimit(2 * token, 3 * token);
+
+      try {
+        await sweep(3 * token);
+        expect.fail("Sweep above the per-transaction cap should fail");
+      } catch (error) {
+        expect(error.message).to.include("RateLimitExceeded");
+      }
+
+      await sweep(2 * token);
+      try {
+        await sweep(2 * token);
+        expect.fail("Sweep above the window cap should fail");
+      } catch (error) {
+        expect(error.message).to.include("RateLimitExceeded");
+      }
+      await sweep(token);
+
+      const limit = await program.account.spendingLimit.fetch(spendingLimitAddress(payer.publicKey));
+      expect(limit.windowSpent.toNumber()).to.equal(3 * token);
+
+      // The admin can tighten its own caps but not raise them
+      try {
+        await setLimit(0, 0);
+        expect.fail("Raising a cap directly should fail");
+      } catch (error) {
+        expect(error.message).to.include("LimitIncreaseNotAllowed");
+      }
+
+      // Lift the caps for the remaining tests through the timelock
+      const delay = 2;
+      await program.methods
+          .enableTimelock(new anchor.BN(delay))
+          .accounts({
+            adminState: adminStateAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      const queuedActionAddress = (actionId: anchor.BN) => PublicKey.findProgramAddressSync(
+          [Buffer.from("queued_action"), actionId.toArrayLike(Buffer, "le", 8)],
+          program.programId
+      )[0];
+      const queueAndExecute = async (actions: any[]) => {
+        const ids: anchor.BN[] = [];
+        for (const action of actions) {
+          const adminState = await program.account.adminState.fetch(adminStateAddress);
+          ids.push(adminState.timelockCount);
+          await program.methods
+              .queueAction(action)
+              .accounts({
+                adminState: adminStateAddress,
+                queuedAction: queuedActionAddress(adminState.timelockCount),
+                admin: payer.publicKey,
+                systemProgram: SystemProgram.programId,
+              })
+              .signers([payer])
+              .rpc();
+        }
+
+        await new Promise((resolve) => setTimeout(resolve, (delay + 1) * 1000));
+
+        for (const actionId of ids) {
+          await program.methods
+              .executeQueuedAction(actionId)
+              .accounts({
+                adminState: adminStateAddress,
+                programConfig: programConfigAddress,
+                queuedAction: queuedActionAddress(actionId),
+                guardianState: null,
+                treasuryState: null,
+                treasuryAta: null,
+                destination: null,
+                userPda: null,
+                allowedDestination: null,
+                admin: payer.publicKey,
+                tokenProgram: null,
+                spendingLimit: spendingLimitAddress(payer.publicKey),
+                systemProgram: null,
+              })
+              .signers([payer])
+              .rpc();
+        }
+      };
+
+      await queueAndExecute([
+        { setSpendingLimit: { key: payer.publicKey, maxPerTx: new anchor.BN(0), maxPerWindow: new anchor.BN(0) } },
+        { updateTimelockDelay: { delay: new anchor.BN(0) } },
+      ]);
+
+      const lifted = await program.account.spendingLimit.fetch(spendingLimitAddress(payer.publicKey));
+      expect(lifted.maxPerTx.toNumber()).to.equal(0);
+      expect(lifted.maxPerWindow.toNumber()).to.equal(0);
+    });
+
+    it("Should refund a swept payment from the treasury to the user", async () => {
+      const refundAmount = 5 * Math.pow(10, 9);
+      const originalTxRef = Array.from(Buffer.alloc(64, 7));
+      const userBefore = await getTokenAccount(connection, userAtaAddress);
+      const treasuryBefore = await getTokenAccount(connection, treasuryAtaAddress);
+
+      await program.methods
+          .refundToUser(TEST_USER_ID, new anchor.BN(refundAmount), 1, originalTxRef)
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: configAddress,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            spendingLimit: spendingLimitAddress(treasuryStateAddress),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      const userAfter = await getTokenAccount(connection, userAtaAddress);
+      const treasuryAfter = await getTokenAccount(connection, treasuryAtaAddress);
+      expect(Number(userAfter.amount)).to.equal(Number(userBefore.amount) + refundAmount);
+      expect(Number(treasuryAfter.amount)).to.equal(Number(treasuryBefore.amount) - refundAmount);
+
+      const userPda = await program.account.userPda.fetch(userPdaAddress);
+      const treasuryState = await program.account.treasuryState.fetch(treasuryStateAddress);
+      expect(userPda.totalRefunded.toNumber()).to.equal(refundAmount);
+      expect(treasuryState.totalRefunded.toNumber()).to.equal(refundAmount);
+    });
+  });
+
+  describe("Subscriptions", () => {
+    const PLAN_ID = new anchor.BN(1);
+    const PLAN_PRICE = 5 * Math.pow(10, 9);
+    const PLAN_PERIOD = 30 * 24 * 60 * 60;
+    const PLAN_GRACE = 3 * 24 * 60 * 60;
+    let adminStateAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let treasuryAtaAddress: PublicKey;
+    let planAddress: PublicKey;
+    let subscriptionAddress: PublicKey;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      treasuryAtaAddress = getAssociatedTokenAddressSync(testMint, treasuryStateAddress, true);
+      [planAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("plan"), PLAN_ID.toArrayLike(Buffer, "le", 8)],
+          program.programId
+      );
+      [subscriptionAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("subscription"), userPdaAddress.toBuffer()],
+          program.programId
+      );
+    });
+
+    const chargeSubscription = () => program.methods
+        .chargeSubscription(TEST_USER_ID)
+        .accounts({
+          programConfig: configAddress,
+          adminState: adminStateAddress,
+          treasuryState: treasuryStateAddress,
+          treasuryAta: treasuryAtaAddress,
+          userPda: userPdaAddress,
+          userPdaAta: userAtaAddress,
+          lock: null,
+          plan: planAddress,
+          subscription: subscriptionAddress,
+          mint: testMint,
+          admin: payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+    it("Should create a plan and subscribe a user to it", async () => {
+      await program.methods
+          .createPlan(PLAN_ID, new anchor.BN(PLAN_PRICE), new anchor.BN(PLAN_PERIOD), new anchor.BN(PLAN_GRACE))
+          .accounts({
+            adminState: adminStateAddress,
+            plan: planAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      await program.methods
+          .createSubscription(TEST_USER_ID)
+          .accounts({
+            userPda: userPdaAddress,
+            plan: planAddress,
+            subscription: subscriptionAddress,
+            owner: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const subscription = await program.account.subscription.fetch(subscriptionAddress);
+      expect(subscription.plan.toString()).to.equal(planAddress.toString());
+      expect(subscription.status).to.deep.equal({ active: {} });
+    });
+
+    it("Should charge a due period into the treasury and refuse an early charge", async () => {
+      const userBefore = await getTokenAccount(connection, userAtaAddress);
+      const treasuryBefore = await getTokenAccount(connection, treasuryAtaAddress);
+      const subscriptionBefore = await program.account.subscription.fetch(subscriptionAddress);
+
+      await chargeSubscription();
+
+      const userAfter = await getTokenAccount(connection, userAtaAddress);
+      const treasuryAfter = await getTokenAccount(connection, treasuryAtaAddress);
+      const subscriptionAfter = await program.account.subscription.fetch(subscriptionAddress);
+
+      expect(Number(userAfter.amount)).to.equal(Number(userBefore.amount) - PLAN_PRICE);
+      expect(Number(treasuryAfter.amount)).to.equal(Number(treasuryBefore.amount) + PLAN_PRICE);
+      expect(subscriptionAfter.nextChargeAt.toNumber()).to.equal(subscriptionBefore.nextChargeAt.toNumber() + PLAN_PERIOD);
+
+      try {
+        await chargeSubscription();
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("SubscriptionNotDue");
+      }
+    });
+
+    it("Should upgrade with prorated credit and refund the unused period on cancel", async () => {
+      const premiumId = new anchor.BN(2);
+      const premiumPrice = 2 * PLAN_PRICE;
+      const [premiumPlan] = PublicKey.findProgramAddressSync(
+          [Buffer.from("plan"), premiumId.toArrayLike(Buffer, "le", 8)],
+          program.programId
+      );
+
+      await program.methods
+          .createPlan(premiumId, new anchor.BN(premiumPrice), new anchor.BN(PLAN_PERIOD), new anchor.BN(PLAN_GRACE))
+          .accounts({
+            adminState: adminStateAddress,
+            plan: premiumPlan,
+            mint: testMint,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      // Almost the whole basic period is unused, so the upgrade costs about one basic price
+      const userBeforeUpgrade = await getTokenAccount(connection, userAtaAddress);
+      await program.methods
+          .changePlan(TEST_USER_ID, false)
+          .accounts({
+            programConfig: configAddress,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            lock: null,
+            plan: planAddress,
+            newPlan: premiumPlan,
+            subscription: subscriptionAddress,
+            mint: testMint,
+            owner: payer.publicKey,
+            spendingLimit: spendingLimitAddress(treasuryStateAddress),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+      const userAfterUpgrade = await getTokenAccount(connection, userAtaAddress);
+      const upgradeCost = Number(userBeforeUpgrade.amount) - Number(userAfterUpgrade.amount);
+      expect(upgradeCost).to.be.closeTo(PLAN_PRICE, PLAN_PRICE / 100);
+
+      const subscription = await program.account.subscription.fetch(subscriptionAddress);
+      expect(subscription.plan.toString()).to.equal(premiumPlan.toString());
+
+      // Cancelling right away refunds close to the full premium period
+      await program.methods
+          .cancelSubscription(TEST_USER_ID)
+          .accounts({
+            programConfig: configAddress,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            plan: premiumPlan,
+            subscription: subscriptionAddress,
+            mint: testMint,
+            owner: payer.publicKey,
+            spendingLimit: spendingLimitAddress(treasuryStateAddress),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+      const userAfterCancel = await getTokenAccount(connection, userAtaAddress);
+      const refund = Number(userAfterCancel.amount) - Number(userAfterUpgrade.amount);
+      expect(refund).to.be.closeTo(premiumPrice, premiumPrice / 100);
+
+      const closed = await connection.getAccountInfo(subscriptionAddress);
+      expect(closed).to.be.null;
+    });
+  });
+
+  describe("Token Sale", () => {
+    const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bWvrR");
+    const SALE_PRICE = Math.pow(10, 9); // one payment token per sale token
+    const SALE_DECIMALS = 6;
+    let adminStateAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let treasuryAtaAddress: PublicKey;
+    let payerPaymentAccount: PublicKey;
+    let saleMint: Keypair;
+    let saleAddress: PublicKey;
+    let metadataAddress: PublicKey;
+    let userSaleAta: PublicKey;
+    let purchaseAddress: PublicKey;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      treasuryAtaAddress = getAssociatedTokenAddressSync(testMint, treasuryStateAddress, true);
+      payerPaymentAccount = getAssociatedTokenAddressSync(testMint, payer.publicKey, false);
+      await mintTo(connection, payer, testMint, payerPaymentAccount, payer.publicKey, 100 * SALE_PRICE);
+
+      saleMint = Keypair.generate();
+      [saleAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("sale"), saleMint.publicKey.toBuffer()],
+          program.programId
+      );
+      [metadataAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), saleMint.publicKey.toBuffer()],
+          TOKEN_METADATA_PROGRAM_ID
+      );
+      userSaleAta = getAssociatedTokenAddressSync(saleMint.publicKey, userPdaAddress, true);
+      [purchaseAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("purchase"), saleAddress.toBuffer(), userPdaAddress.toBuffer()],
+          program.programId
+      );
+    });
+
+    it("Should create a token with metadata and open a sale", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      // Soft cap above anything bought here, so the sale fails and refunds can be tested
+      await program.methods
+          .initiateToken(
+              { name: "Sale Token", symbol: "SALE", uri: "https://example.com/sale.json", decimals: SALE_DECIMALS },
+              new anchor.BN(now - 10),
+              new anchor.BN(now + 8),
+              new anchor.BN(SALE_PRICE),
+              new anchor.BN(1000 * SALE_PRICE),
+              new anchor.BN(50 * Math.pow(10, SALE_DECIMALS)),
+          )
+          .accounts({
+            adminState: adminStateAddress,
+            sale: saleAddress,
+            mint: saleMint.publicKey,
+            metadata: metadataAddress,
+            paymentMint: testMint,
+            treasuryState: treasuryStateAddress,
+            admin: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          })
+          .signers([payer, saleMint])
+          .rpc();
+
+      const sale = await program.account.sale.fetch(saleAddress);
+      expect(sale.mint.toString()).to.equal(saleMint.publicKey.toString());
+      expect(sale.status).to.deep.equal({ active: {} });
+      expect(await connection.getAccountInfo(metadataAddress)).to.not.be.null;
+    });
+
+    it("Should take payment into the treasury and mint sale tokens to the user PDA", async () => {
+      const amount = 10 * Math.pow(10, SALE_DECIMALS);
+      const treasuryBefore = await getTokenAccount(connection, treasuryAtaAddress);
+
+      await program.methods
+          .buy(TEST_USER_ID, new anchor.BN(amount))
+          .accounts({
+            programConfig: configAddress,
+            sale: saleAddress,
+            saleMint: saleMint.publicKey,
+            paymentMint: testMint,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            userPda: userPdaAddress,
+            userSaleAta: userSaleAta,
+            buyerPaymentAccount: payerPaymentAccount,
+            purchase: purchaseAddress,
+            buyer: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const treasuryAfter = await getTokenAccount(connection, treasuryAtaAddress);
+      const saleTokens = await getTokenAccount(connection, userSaleAta);
+      expect(Number(treasuryAfter.amount)).to.equal(Number(treasuryBefore.amount) + 10 * SALE_PRICE);
+      expect(Number(saleTokens.amount)).to.equal(amount);
+    });
+
+    it("Should fail the sale below the soft cap and refund the purchase", async () => {
+      await new Promise(resolve => setTimeout(resolve, 10000));
+
+      await program.methods
+          .finalizeSale()
+          .accounts({
+            adminState: adminStateAddress,
+            sale: saleAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      const sale = await program.account.sale.fetch(saleAddress);
+      expect(sale.status).to.deep.equal({ failed: {} });
+
+      const userBefore = await getTokenAccount(connection, userAtaAddress);
+      await program.methods
+          .refundSalePurchase(TEST_USER_ID)
+          .accounts({
+            programConfig: configAddress,
+            sale: saleAddress,
+            saleMint: saleMint.publicKey,
+            paymentMint: testMint,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            userSaleAta: userSaleAta,
+            purchase: purchaseAddress,
+            owner: payer.publicKey,
+            spendingLimit: spendingLimitAddress(treasuryStateAddress),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      const userAfter = await getTokenAccount(connection, userAtaAddress);
+      const saleTokens = await getTokenAccount(connection, userSaleAta);
+      expect(Number(userAfter.amount)).to.equal(Number(userBefore.amount) + 10 * SALE_PRICE);
+      expect(Number(saleTokens.amount)).to.equal(0);
+      expect(await connection.getAccountInfo(purchaseAddress)).to.be.null;
+    });
+  });
+
+  describe("Escrow", () => {
+    const SELLER_USER_ID = "TierUser";
+    const ESCROW_AMOUNT = 2 * Math.pow(10, 9);
+    let adminStateAddress: PublicKey;
+    let sellerPda: PublicKey;
+    let sellerAta: PublicKey;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [sellerPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("deposit"), Buffer.from(SELLER_USER_ID)],
+          program.programId
+      );
+      sellerAta = getAssociatedTokenAddressSync(testMint, sellerPda, true);
+    });
+
+    // Escrow ids are scoped to the buyer and seller pair
+    const escrowAccounts = (escrowId: anchor.BN) => {
+      const [escrow] = PublicKey.findProgramAddressSync(
+          [Buffer.from("escrow"), userPdaAddress.toBuffer(), sellerPda.toBuffer(), escrowId.toArrayLike(Buffer, "le", 8)],
+          program.programId
+      );
+      return { escrow, escrowAta: getAssociatedTokenAddressSync(testMint, escrow, true) };
+    };
+
+    const fundEscrow = (escrowId: anchor.BN) => program.methods
+        .fundEscrow(escrowId, TEST_USER_ID, new anchor.BN(ESCROW_AMOUNT))
+        .accounts({
+          programConfig: configAddress,
+          ...escrowAccounts(escrowId),
+          buyerPda: userPdaAddress,
+          buyerPdaAta: userAtaAddress,
+          lock: null,
+          sellerPda: sellerPda,
+          mint: testMint,
+          owner: payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+
+    it("Should hold the buyer's funds and release them to the seller", async () => {
+      const escrowId = new anchor.BN(1);
+      const { escrow, escrowAta } = escrowAccounts(escrowId);
+      const buyerBefore = await getTokenAccount(connection, userAtaAddress);
+      const sellerBefore = await getTokenAccount(connection, sellerAta);
+
+      await fundEscrow(escrowId);
+
+      const held = await getTokenAccount(connection, escrowAta);
+      const buyerAfterFund = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(held.amount)).to.equal(ESCROW_AMOUNT);
+      expect(Number(buyerAfterFund.amount)).to.equal(Number(buyerBefore.amount) - ESCROW_AMOUNT);
+
+      const release = () => program.methods
+          .releaseEscrow(escrowId)
+          .accounts({
+            programConfig: configAddress,
+            escrow,
+            escrowAta,
+            buyerPda: userPdaAddress,
+            sellerPda: sellerPda,
+            sellerPdaAta: sellerAta,
+            mint: testMint,
+            owner: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      await release();
+
+      const sellerAfter = await getTokenAccount(connection, sellerAta);
+      expect(Number(sellerAfter.amount)).to.equal(Number(sellerBefore.amount) + ESCROW_AMOUNT);
+      const escrowAccount = await program.account.escrow.fetch(escrow);
+      expect(escrowAccount.status).to.deep.equal({ released: {} });
+
+      try {
+        await release();
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("EscrowAlreadySettled");
+      }
+    });
+
+    it("Should let the admin resolve a dispute in the buyer's favour", async () => {
+      const escrowId = new anchor.BN(2);
+      const { escrow, escrowAta } = escrowAccounts(escrowId);
+      const buyerBefore = await getTokenAccount(connection, userAtaAddress);
+
+      await fundEscrow(escrowId);
+
+      await program.methods
+          .resolveEscrowDispute(escrowId, false)
+          .accounts({
+            programConfig: configAddress,
+            adminState: adminStateAddress,
+            escrow,
+            escrowAta,
+            buyerPda: userPdaAddress,
+            buyerPdaAta: userAtaAddress,
+            sellerPda: sellerPda,
+            sellerPdaAta: sellerAta,
+            mint: testMint,
+            admin: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      const buyerAfter = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(buyerAfter.amount)).to.equal(Number(buyerBefore.amount));
+      const escrowAccount = await program.account.escrow.fetch(escrow);
+      expect(escrowAccount.status).to.deep.equal({ refunded: {} });
+    });
+  });
+
+  describe("Transfers Between Users", () => {
+    const TO_USER_ID = "TierUser";
+    let programConfigAddress: PublicKey;
+    let toPda: PublicKey;
+    let toAta: PublicKey;
+
+    before(async () => {
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+      [toPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("deposit"), Buffer.from(TO_USER_ID)],
+          program.programId
+      );
+      toAta = getAssociatedTokenAddressSync(testMint, toPda, true);
+    });
+
+    const transfer = (amount: number, authority: Keypair = payer) => program.methods
+        .transferBetweenUsers(TEST_USER_ID, TO_USER_ID, new anchor.BN(amount))
+        .accounts({
+          programConfig: programConfigAddress,
+          fromPda: userPdaAddress,
+          fromPdaAta: userAtaAddress,
+          lock: null,
+          toPda: toPda,
+          toPdaAta: toAta,
+          mint: testMint,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+    it("Should reject transfers not signed by the source account owner", async () => {
+      try {
+        await transfer(Math.pow(10, 9), Keypair.generate());
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedUserOwner");
+      }
+    });
+
+    it("Should move tokens between two deposit accounts within the receiver's limits", async () => {
+      const fromBefore = await getTokenAccount(connection, userAtaAddress);
+      const toBefore = await getTokenAccount(connection, toAta);
+      const amount = 3 * Math.pow(10, 9);
+
+      await transfer(amount);
+
+      const fromAfter = await getTokenAccount(connection, userAtaAddress);
+      const toAfter = await getTokenAccount(connection, toAta);
+      expect(Number(fromAfter.amount)).to.equal(Number(fromBefore.amount) - amount);
+      expect(Number(toAfter.amount)).to.equal(Number(toBefore.amount) + amount);
+
+      // The receiver's tier 1 daily limit (10 tokens) already holds 9
+      try {
+        await transfer(2 * Math.pow(10, 9));
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("DepositLimitExceeded");
+      }
+    });
+  });
+
+  describe("Withdrawal Vouchers", () => {
+    let adminStateAddress: PublicKey;
+    let destination: PublicKey;
+    const voucherSigner = Keypair.generate();
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      destination = getAssociatedTokenAddressSync(testMint, payer.publicKey, false);
+    });
+
+    const setVoucherSigner = (signerKey: PublicKey) => program.methods
+        .updateVoucherSigner(signerKey)
+        .accounts({
+          adminState: adminStateAddress,
+          programConfig: configAddress,
+          admin: payer.publicKey,
+        })
+        .signers([payer])
+        .rpc();
+
+    // Borsh layout of the on-chain `Voucher` struct
+    const encodeVoucher = (userId: string, amount: anchor.BN, dest: PublicKey, nonce: anchor.BN, expiry: anchor.BN) => {
+      const userIdBytes = Buffer.from(userId);
+      const length = Buffer.alloc(4);
+      length.writeUInt32LE(userIdBytes.length);
+      return Buffer.concat([
+        program.programId.toBuffer(),
+        length,
+        userIdBytes,
+        testMint.toBuffer(),
+        amount.toArrayLike(Buffer, "le", 8),
+        dest.toBuffer(),
+        nonce.toArrayLike(Buffer, "le", 8),
+        expiry.toTwos(64).toArrayLike(Buffer, "le", 8),
+      ]);
+    };
+
+    const redeem = (signer: Keypair, amount: anchor.BN, nonce: anchor.BN, expiry: anchor.BN) => {
+      const [redemption] = PublicKey.findProgramAddressSync(
+          [Buffer.from("voucher"), userPdaAddress.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+          program.programId
+      );
+      const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signer.secretKey,
+        message: encodeVoucher(TEST_USER_ID, amount, destination, nonce, expiry),
+      });
+
+      return program.methods
+          .redeemVoucher(TEST_USER_ID, amount, nonce, expiry)
+          .accounts({
+            programConfig: configAddress,
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            lock: null,
+            attestation: null,
+            destination,
+            redemption,
+            mint: testMint,
+            owner: payer.publicKey,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .preInstructions([ed25519Ix])
+          .signers([payer])
+          .rpc();
+    };
+
+    it("Should reject vouchers until a voucher signer is configured", async () => {
+      try {
+        await redeem(payer, new anchor.BN(Math.pow(10, 9)), new anchor.BN(1), new anchor.BN(Math.floor(Date.now() / 1000) + 600));
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("VoucherSignerNotSet");
+      }
+
+      await setVoucherSigner(voucherSigner.publicKey);
+      const config = await program.account.programConfig.fetch(configAddress);
+      expect(config.voucherSigner.toString()).to.equal(voucherSigner.publicKey.toString());
+    });
+
+    it("Should withdraw with a signed voucher exactly once", async () => {
+      const amount = new anchor.BN(Math.pow(10, 9));
+      const nonce = new anchor.BN(1);
+      const expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 600);
+      const userBefore = await getTokenAccount(connection, userAtaAddress);
+      const destinationBefore = await getTokenAccount(connection, destination);
+
+      await redeem(voucherSigner, amount, nonce, expiry);
+
+      const userAfter = await getTokenAccount(connection, userAtaAddress);
+      const destinationAfter = await getTokenAccount(connection, destination);
+      expect(Number(userAfter.amount)).to.equal(Number(userBefore.amount) - amount.toNumber());
+      expect(Number(destinationAfter.amount)).to.equal(Number(destinationBefore.amount) + amount.toNumber());
+
+      try {
+        await redeem(voucherSigner, amount, nonce, expiry);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("VoucherAlreadyRedeemed");
+      }
+    });
+
+    it("Should reject vouchers not signed by the voucher signer or past their expiry", async () => {
+      const amount = new anchor.BN(Math.pow(10, 9));
+
+      // The admin key can no longer sign vouchers
+      try {
+        await redeem(payer, amount, new anchor.BN(2), new anchor.BN(Math.floor(Date.now() / 1000) + 600));
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("InvalidVoucherSignature");
+      }
+
+      try {
+        await redeem(voucherSigner, amount, new anchor.BN(3), new anchor.BN(Math.floor(Date.now() / 1000) - 60));
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("VoucherExpired");
+      }
+    });
+  });
+
+  describe("Merkle Distributions", () => {
+    const DISTRIBUTION_ID = new anchor.BN(1);
+    const OTHER_USER_ID = "TierUser";
+    const USER_REWARD = 3 * Math.pow(10, 9);
+    const OTHER_REWARD = 4 * Math.pow(10, 9);
+    let adminStateAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let treasuryAtaAddress: PublicKey;
+    let distributionAddress: PublicKey;
+    let vaultAddress: PublicKey;
+    let leaves: Buffer[];
+    let root: Buffer;
+
+    // keccak(index_le, user_id, amount_le), matching the on-chain leaf
+    const leaf = (index: number, userId: string, amount: number) => {
+      const indexBytes = Buffer.alloc(4);
+      indexBytes.writeUInt32LE(index);
+      return Buffer.from(keccak_256(Buffer.concat([
+        indexBytes,
+        Buffer.from(userId),
+        new anchor.BN(amount).toArrayLike(Buffer, "le", 8),
+      ])));
+    };
+    const hashPair = (a: Buffer, b: Buffer) =>
+        Buffer.from(keccak_256(Buffer.compare(a, b) <= 0 ? Buffer.concat([a, b]) : Buffer.concat([b, a])));
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      treasuryAtaAddress = getAssociatedTokenAddressSync(testMint, treasuryStateAddress, true);
+      [distributionAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("distribution"), DISTRIBUTION_ID.toArrayLike(Buffer, "le", 8)],
+          program.programId
+      );
+      vaultAddress = getAssociatedTokenAddressSync(testMint, distributionAddress, true);
+
+      leaves = [leaf(0, TEST_USER_ID, USER_REWARD), leaf(1, OTHER_USER_ID, OTHER_REWARD)];
+      root = hashPair(leaves[0], leaves[1]);
+    });
+
+    const claim = () => program.methods
+        .claim(DISTRIBUTION_ID, 0, TEST_USER_ID, new anchor.BN(USER_REWARD), [Array.from(leaves[1])])
+        .accounts({
+          programConfig: configAddress,
+          distribution: distributionAddress,
+          vault: vaultAddress,
+          userPda: userPdaAddress,
+          userPdaAta: userAtaAddress,
+          mint: testMint,
+          claimant: payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+    it("Should fund a distribution from the treasury and credit a valid claim once", async () => {
+      const total = USER_REWARD + OTHER_REWARD;
+      await program.methods
+          .createDistribution(
+              DISTRIBUTION_ID,
+              Array.from(root),
+              new anchor.BN(total),
+              2,
+              new anchor.BN(Math.floor(Date.now() / 1000) + 8),
+          )
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: configAddress,
+            distribution: distributionAddress,
+            vault: vaultAddress,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            spendingLimit: spendingLimitAddress(treasuryStateAddress),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const vault = await getTokenAccount(connection, vaultAddress);
+      expect(Number(vault.amount)).to.equal(total);
+
+      const userBefore = await getTokenAccount(connection, userAtaAddress);
+      await claim();
+      const userAfter = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(userAfter.amount)).to.equal(Number(userBefore.amount) + USER_REWARD);
+
+      try {
+        await claim();
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("AlreadyClaimed");
+      }
+    });
+
+    it("Should claw back unclaimed rewards after expiry", async () => {
+      await new Promise(resolve => setTimeout(resolve, 10000));
+      const treasuryBefore = await getTokenAccount(connection, treasuryAtaAddress);
+
+      await program.methods
+          .clawback(DISTRIBUTION_ID)
+          .accounts({
+            programConfig: configAddress,
+            adminState: adminStateAddress,
+            distribution: distributionAddress,
+            vault: vaultAddress,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            mint: testMint,
+            admin: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+      const treasuryAfter = await getTokenAccount(connection, treasuryAtaAddress);
+      expect(Number(treasuryAfter.amount)).to.equal(Number(treasuryBefore.amount) + OTHER_REWARD);
+
+      try {
+        await program.methods
+            .clawback(DISTRIBUTION_ID)
+            .accounts({
+              programConfig: configAddress,
+              adminState: adminStateAddress,
+              distribution: distributionAddress,
+              vault: vaultAddress,
+              treasuryState: treasuryStateAddress,
+              treasuryAta: treasuryAtaAddress,
+              mint: testMint,
+              admin: payer.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([payer])
+            .rpc();
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.message).to.include("DistributionAlreadyClawedBack");
+      }
+    });
+  });
+
+  describe("Referrals", () => {
+    const REFERRED_USER_ID = "ReferredUser";
+    let adminStateAddress: PublicKey;
+    let programConfigAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let treasuryAtaAddress: PublicKey;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      treasuryAtaAddress = getAssociatedTokenAddressSync(testMint, treasuryStateAddress, true);
+    });
+
+    const setReferralBps = (bps: number) => program.methods
+        .updateReferralConfig(bps)
+        .accounts({
+          adminState: adminStateAddress,
+          programConfig: programConfigAddress,
+          admin: payer.publicKey,
+        })
+        .signers([payer])
+        .rpc();
+
+    it("Should accrue referral rewards on referred deposits and pay them from the treasury", async () => {
+      const [referredPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("deposit"), Buffer.from(REFERRED_USER_ID)],
+          program.programId
+      );
+      const referredAta = getAssociatedTokenAddressSync(testMint, referredPda, true);
+
+      await setReferralBps(100); // 1%
+
+      await program.methods
+          .createUserDepositAccount(REFERRED_USER_ID, TEST_USER_ID)
+          .accounts({
+            userPda: referredPda,
+            userAta: referredAta,
+            subscriptionTokenMint: testMint,
+            referrerPda: userPdaAddress,
+            payer: payer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const referrerBefore = await program.account.userPda.fetch(userPdaAddress);
+      const depositAmount = 10 * Math.pow(10, 9);
+      await program.methods
+          .depositTokens(REFERRED_USER_ID, new anchor.BN(depositAmount), null, null, null, null)
+          .accounts({
+            userPda: referredPda,
+            userPdaAta: referredAta,
+            userTokenAccount: getAssociatedTokenAddressSync(testMint, payer.publicKey, false),
+            mint: testMint,
+            treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
+            feeVault: null,
+            user: payer.publicKey,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            attestation: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            referenceReceipt: null,
+            depositReceipt: null,
+            lock: null,
+            adminState: null,
+            referrerPda: userPdaAddress,
+          })
+          .signers([payer])
+          .rpc();
+
+      const referrerAfter = await program.account.userPda.fetch(userPdaAddress);
+      const reward = depositAmount / 100;
+      expect(referrerAfter.referredVolume.toNumber()).to.equal(referrerBefore.referredVolume.toNumber() + depositAmount);
+      expect(referrerAfter.referralRewards.toNumber()).to.equal(referrerBefore.referralRewards.toNumber() + reward);
+
+      const userBefore = await getTokenAccount(connection, userAtaAddress);
+      await program.methods
+          .claimReferralRewards(TEST_USER_ID)
+          .accounts({
+            programConfig: configAddress,
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            userPda: userPdaAddress,
+            userPdaAta: userAtaAddress,
+            mint: testMint,
+            owner: payer.publicKey,
+            spendingLimit: spendingLimitAddress(treasuryStateAddress),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+      const userAfter = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(userAfter.amount)).to.equal(Number(userBefore.amount) + referrerAfter.referralRewards.toNumber());
+
+      const referrerClaimed = await program.account.userPda.fetch(userPdaAddress);
+      expect(referrerClaimed.referralRewards.toNumber()).to.equal(0);
+
+      await setReferralBps(0);
+    });
+  });
+
+  describe("Admin Multisig", () => {
+    let adminStateAddress: PublicKey;
+    let adminSetAddress: PublicKey;
+    let programConfigAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let treasuryAtaAddress: PublicKey;
+    let secondMember: Keypair;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [adminSetAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin_set")],
+          program.programId
+      );
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      treasuryAtaAddress = getAssociatedTokenAddressSync(testMint, treasuryStateAddress, true);
+      secondMember = Keypair.generate();
+    });
+
+    const proposalAddress = (proposalId: anchor.BN) => PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), proposalId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+    )[0];
+
+    // Propose with the payer and approve with the second member
+    const proposeAndApprove = async (action: any) => {
+      const adminSet = await program.account.adminSet.fetch(adminSetAddress);
+      const proposalId = adminSet.proposalCount;
+      const proposal = proposalAddress(proposalId);
+
+      await program.methods
+          .proposeAction(action)
+          .accounts({
+            adminState: adminStateAddress,
+            adminSet: adminSetAddress,
+            proposal,
+            proposer: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      await program.methods
+          .approveAction(proposalId)
+          .accounts({
+            adminSet: adminSetAddress,
+            proposal,
+            approver: secondMember.publicKey,
+          })
+          .signers([secondMember])
+          .rpc();
+
+      return proposalId;
+    };
+
+    const execute = (proposalId: anchor.BN, extraAccounts: any) => program.methods
+        .executeAction(proposalId)
+        .accounts({
+          adminState: adminStateAddress,
+          adminSet: adminSetAddress,
+          programConfig: programConfigAddress,
+          proposal: proposalAddress(proposalId),
+          guardianState: null,
+          treasuryState: null,
+          treasuryAta: null,
+          destination: null,
+          userPda: null,
+          allowedDestination: null,
+          executor: payer.publicKey,
+          tokenProgram: null,
+          spendingLimit: null,
+          systemProgram: null,
+          ...extraAccounts,
+        })
+        .signers([payer])
+        .rpc();
+
+    const runProposal = async (action: any, extraAccounts: any) => {
+      const proposalId = await proposeAndApprove(action);
+      await execute(proposalId, extraAccounts);
+      return proposalAddress(proposalId);
+    };
+
+    it("Should route privileged actions through an M-of-N admin set", async () => {
+      // Allowlist the payout destination owner; it only becomes usable after the delay
+      const [allowedDestination] = PublicKey.findProgramAddressSync(
+          [Buffer.from("allowed_destination"), testMint.toBuffer(), payer.publicKey.toBuffer()],
+          program.programId
+      );
+      await program.methods
+          .updateDestinationDelay(new anchor.BN(10))
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: programConfigAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+      await program.methods
+          .addAllowedDestination(testMint, payer.publicKey)
+          .accounts({
+            adminState: adminStateAddress,
+            programConfig: programConfigAddress,
+            allowedDestination,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      await program.methods
+          .configureAdminSet([payer.publicKey, secondMember.publicKey], 2)
+          .accounts({
+            adminState: adminStateAddress,
+            adminSet: adminSetAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const adminInfo = await program.account.adminState.fetch(adminStateAddress);
+      expect(adminInfo.multisigEnabled).to.equal(true);
+
+      // Direct admin changes are rejected while the multisig is on
+      try {
+        await program.methods
+            .updateReferralConfig(100)
+            .accounts({
+              adminState: adminStateAddress,
+              programConfig: programConfigAddress,
+              admin: payer.publicKey,
+            })
+            .signers([payer])
+            .rpc();
+        expect.fail("Direct update should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("MultisigRequired");
+      }
+
+      const destination = getAssociatedTokenAddressSync(testMint, payer.publicKey, false);
+      const payoutAmount = 1000;
+      const before = await getTokenAccount(connection, destination);
+
+      const payoutAccounts = {
+        treasuryState: treasuryStateAddress,
+        treasuryAta: treasuryAtaAddress,
+        destination,
+        allowedDestination,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        spendingLimit: spendingLimitAddress(treasuryStateAddress),
+      };
+      const proposalId = await proposeAndApprove(
+          { treasuryPayout: { mint: testMint, destination, amount: new anchor.BN(payoutAmount) } }
+      );
+
+      try {
+        await execute(proposalId, payoutAccounts);
+        expect.fail("Payout before the destination activates should fail");
+      } catch (error) {
+        expect(error.message).to.include("DestinationNotActive");
+      }
+
+      const entry = await program.account.allowedDestination.fetch(allowedDestination);
+      const waitMs = (entry.activatesAt.toNumber() + 1) * 1000 - Date.now();
+      await new Promise((resolve) => setTimeout(resolve, Math.max(waitMs, 0)));
+
+      await execute(proposalId, payoutAccounts);
+      const proposal = proposalAddress(proposalId);
+
+      const after = await getTokenAccount(connection, destination);
+      expect(Number(after.amount)).to.equal(Number(before.amount) + payoutAmount);
+
+      const executed = await program.account.proposal.fetch(proposal);
+      expect(executed.executed).to.equal(true);
+      expect(executed.approvalCount).to.equal(2);
+
+      // Refunds need the admin set as well
+      const refundAmount = 500;
+      const originalTxRef = Array.from(Buffer.alloc(64, 9));
+      try {
+        await program.methods
+            .refundToUser(TEST_USER_ID, new anchor.BN(refundAmount), 2, originalTxRef)
+            .accounts({
+              adminState: adminStateAddress,
+              programConfig: configAddress,
+              treasuryState: treasuryStateAddress,
+              treasuryAta: treasuryAtaAddress,
+              userPda: userPdaAddress,
+              userPdaAta: userAtaAddress,
+              mint: testMint,
+              admin: payer.publicKey,
+              spendingLimit: spendingLimitAddress(treasuryStateAddress),
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([payer])
+            .rpc();
+        expect.fail("Direct refund should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("MultisigRequired");
+      }
+
+      const userBefore = await getTokenAccount(connection, userAtaAddress);
+      await runProposal(
+          {
+            refundToUser: {
+              userId: TEST_USER_ID,
+              mint: testMint,
+              amount: new anchor.BN(refundAmount),
+              reasonCode: 2,
+              originalTxRef,
+            },
+          },
+          {
+            treasuryState: treasuryStateAddress,
+            treasuryAta: treasuryAtaAddress,
+            destination: userAtaAddress,
+            userPda: userPdaAddress,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            spendingLimit: spendingLimitAddress(treasuryStateAddress),
+          }
+      );
+      const userAfter = await getTokenAccount(connection, userAtaAddress);
+      expect(Number(userAfter.amount)).to.equal(Number(userBefore.amount) + refundAmount);
+
+      // Hand control back to the single admin key for the remaining tests
+      await runProposal({ updateAdminSet: { members: [], threshold: 0 } }, {});
+
+      const restored = await program.account.adminState.fetch(adminStateAddress);
+      expect(restored.multisigEnabled).to.equal(false);
+    });
+  });
+
+  describe("Timelock", () => {
+    const TIMELOCK_DELAY = 2;
+    let adminStateAddress: PublicKey;
+    let programConfigAddress: PublicKey;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+    });
+
+    const queuedActionAddress = (actionId: anchor.BN) => PublicKey.findProgramAddressSync(
+        [Buffer.from("queued_action"), actionId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+    )[0];
+
+    const queue = async (action: any) => {
+      const adminState = await program.account.adminState.fetch(adminStateAddress);
+      const actionId = adminState.timelockCount;
+      await program.methods
+          .queueAction(action)
+          .accounts({
+            adminState: adminStateAddress,
+            queuedAction: queuedActionAddress(actionId),
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+      return actionId;
+    };
+
+    const execute = (actionId: anchor.BN) => program.methods
+        .executeQueuedAction(actionId)
+        .accounts({
+          adminState: adminStateAddress,
+          programConfig: programConfigAddress,
+          queuedAction: queuedActionAddress(actionId),
+          guardianState: null,
+          treasuryState: null,
+          treasuryAta: null,
+          destination: null,
+          userPda: null,
+          allowedDestination: null,
+          admin: payer.publicKey,
+          tokenProgram: null,
+          spendingLimit: null,
+          systemProgram: null,
+        })
+        .signers([payer])
+        .rpc();
+
+    it("Should delay queued changes and allow cancelling them", async () => {
+      await program.methods
+          .enableTimelock(new anchor.BN(TIMELOCK_DELAY))
+          .accounts({
+            adminState: adminStateAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      // Direct changes are rejected while the timelock is on
+      try {
+        await program.methods
+            .updateReferralConfig(50)
+            .accounts({
+              adminState: adminStateAddress,
+              programConfig: programConfigAddress,
+              admin: payer.publicKey,
+            })
+            .signers([payer])
+            .rpc();
+        expect.fail("Direct update should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("TimelockRequired");
+      }
 
-      console.log("✅ Admin transfer to treasury completed successfully");
+      const referralId = await queue({ updateReferralConfig: { referralBps: 50 } });
+      try {
+        await execute(referralId);
+        expect.fail("Execution before the eta should fail");
+      } catch (error) {
+        expect(error.message).to.include("TimelockNotReady");
+      }
+
+      const cancelledId = await queue({ updateReferralConfig: { referralBps: 100 } });
+      await program.methods
+          .cancelQueuedAction(cancelledId)
+          .accounts({
+            adminState: adminStateAddress,
+            guardianState: null,
+            queuedAction: queuedActionAddress(cancelledId),
+            queuedBy: payer.publicKey,
+            authority: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+      expect(await connection.getAccountInfo(queuedActionAddress(cancelledId))).to.be.null;
+
+      // Turning the timelock off has to wait out the delay as well
+      const resetId = await queue({ updateReferralConfig: { referralBps: 0 } });
+      const disableId = await queue({ updateTimelockDelay: { delay: new anchor.BN(0) } });
+
+      await new Promise((resolve) => setTimeout(resolve, (TIMELOCK_DELAY + 1) * 1000));
+
+      await execute(referralId);
+      let config = await program.account.programConfig.fetch(programConfigAddress);
+      expect(config.referralBps).to.equal(50);
+
+      await execute(resetId);
+      await execute(disableId);
+      config = await program.account.programConfig.fetch(programConfigAddress);
+      expect(config.referralBps).to.equal(0);
+
+      const adminState = await program.account.adminState.fetch(adminStateAddress);
+      expect(adminState.timelockDelay.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Guardian", () => {
+    let adminStateAddress: PublicKey;
+    let guardianStateAddress: PublicKey;
+    let programConfigAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let guardian: Keypair;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [guardianStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("guardian")],
+          program.programId
+      );
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      guardian = Keypair.generate();
+
+      await program.methods
+          .setGuardian(guardian.publicKey)
+          .accounts({
+            adminState: adminStateAddress,
+            guardianState: guardianStateAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+    });
+
+    const deposit = () => program.methods
+        .depositTokens(TEST_USER_ID, new anchor.BN(Math.pow(10, 9)), null, null, null, null)
+        .accounts({
+          userPda: userPdaAddress,
+          userPdaAta: userAtaAddress,
+          userTokenAccount: getAssociatedTokenAddressSync(testMint, payer.publicKey, false),
+          mint: testMint,
+          treasuryState: treasuryStateAddress,
+          programConfig: programConfigAddress,
+          feeVault: null,
+          user: payer.publicKey,
+          blockedWallet: blockedWalletAddress(payer.publicKey),
+          attestation: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          referenceReceipt: null,
+          depositReceipt: null,
+          lock: null,
+          adminState: null,
+          referrerPda: null,
+        })
+        .signers([payer])
+        .rpc();
+
+    const setFrozen = (frozen: boolean, authority: Keypair) => program.methods
+        .setUserFrozen(TEST_USER_ID, frozen)
+        .accounts({
+          adminState: adminStateAddress,
+          guardianState: guardianStateAddress,
+          userPda: userPdaAddress,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    const setPaused = (paused: boolean, authority: Keypair) => program.methods
+        .setPaused(paused)
+        .accounts({
+          adminState: adminStateAddress,
+          guardianState: guardianStateAddress,
+          programConfig: programConfigAddress,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    it("Should let the admin freeze a user but only the guardian unfreeze", async () => {
+      await setFrozen(true, payer);
+
+      try {
+        await deposit();
+        expect.fail("Deposit into a frozen account should fail");
+      } catch (error) {
+        expect(error.message).to.include("AccountFrozen");
+      }
+
+      try {
+        await setFrozen(false, payer);
+        expect.fail("Admin should not be able to unfreeze");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedGuardian");
+      }
+
+      await setFrozen(false, guardian);
+      const info = await program.account.userPda.fetch(userPdaAddress);
+      expect(info.frozen).to.equal(false);
+    });
+
+    it("Should let the guardian pause and resume the program", async () => {
+      await setPaused(true, guardian);
+
+      try {
+        await deposit();
+        expect.fail("Deposit while paused should fail");
+      } catch (error) {
+        expect(error.message).to.include("ProgramPaused");
+      }
+
+      // Treasury outflows stop as well
+      try {
+        await program.methods
+            .refundToUser(TEST_USER_ID, new anchor.BN(1), 3, Array.from(Buffer.alloc(64)))
+            .accounts({
+              adminState: adminStateAddress,
+              programConfig: programConfigAddress,
+              treasuryState: treasuryStateAddress,
+              treasuryAta: getAssociatedTokenAddressSync(testMint, treasuryStateAddress, true),
+              userPda: userPdaAddress,
+              userPdaAta: userAtaAddress,
+              mint: testMint,
+              admin: payer.publicKey,
+              spendingLimit: spendingLimitAddress(treasuryStateAddress),
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([payer])
+            .rpc();
+        expect.fail("Refund while paused should fail");
+      } catch (error) {
+        expect(error.message).to.include("ProgramPaused");
+      }
+
+      try {
+        await setPaused(false, payer);
+        expect.fail("Admin should not be able to resume");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedGuardian");
+      }
+
+      await setPaused(false, guardian);
+      await deposit();
+
+      const config = await program.account.programConfig.fetch(programConfigAddress);
+      expect(config.paused).to.equal(false);
+    });
+  });
+
+  describe("Depositor Blocklist", () => {
+    let adminStateAddress: PublicKey;
+    let programConfigAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+    });
+
+    const deposit = () => program.methods
+        .depositTokens(TEST_USER_ID, new anchor.BN(Math.pow(10, 9)), null, null, null, null)
+        .accounts({
+          userPda: userPdaAddress,
+          userPdaAta: userAtaAddress,
+          userTokenAccount: getAssociatedTokenAddressSync(testMint, payer.publicKey, false),
+          mint: testMint,
+          treasuryState: treasuryStateAddress,
+          programConfig: programConfigAddress,
+          feeVault: null,
+          user: payer.publicKey,
+          blockedWallet: blockedWalletAddress(payer.publicKey),
+          attestation: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          referenceReceipt: null,
+          depositReceipt: null,
+          lock: null,
+          adminState: null,
+          referrerPda: null,
+        })
+        .signers([payer])
+        .rpc();
+
+    it("Should reject deposits from a blocked wallet until it is unblocked", async () => {
+      await program.methods
+          .blockWallet(payer.publicKey, 7)
+          .accounts({
+            adminState: adminStateAddress,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      const entry = await program.account.blockedWallet.fetch(blockedWalletAddress(payer.publicKey));
+      expect(entry.reasonCode).to.equal(7);
+
+      try {
+        await deposit();
+        expect.fail("Deposit from a blocked wallet should fail");
+      } catch (error) {
+        expect(error.message).to.include("DepositorBlocked");
+      }
+
+      await program.methods
+          .unblockWallet(payer.publicKey)
+          .accounts({
+            adminState: adminStateAddress,
+            blockedWallet: blockedWalletAddress(payer.publicKey),
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      await deposit();
+    });
+  });
+
+  describe("KYC Attestations", () => {
+    let adminStateAddress: PublicKey;
+    let programConfigAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+    let attestationAddress: PublicKey;
+    let attester: Keypair;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+      [attestationAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("attestation"), userPdaAddress.toBuffer()],
+          program.programId
+      );
+      attester = Keypair.generate();
+    });
+
+    const setKycConfig = (attesterKey: PublicKey, threshold: number, minLevel: number) => program.methods
+        .updateKycConfig(attesterKey, new anchor.BN(threshold), minLevel)
+        .accounts({
+          adminState: adminStateAddress,
+          programConfig: programConfigAddress,
+          admin: payer.publicKey,
+        })
+        .signers([payer])
+        .rpc();
+
+    const deposit = (amount: number, attestation: PublicKey | null) => program.methods
+        .depositTokens(TEST_USER_ID, new anchor.BN(amount), null, null, null, null)
+        .accounts({
+          userPda: userPdaAddress,
+          userPdaAta: userAtaAddress,
+          userTokenAccount: getAssociatedTokenAddressSync(testMint, payer.publicKey, false),
+          mint: testMint,
+          treasuryState: treasuryStateAddress,
+          programConfig: programConfigAddress,
+          feeVault: null,
+          user: payer.publicKey,
+          blockedWallet: blockedWalletAddress(payer.publicKey),
+          attestation,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          referenceReceipt: null,
+          depositReceipt: null,
+          lock: null,
+          adminState: null,
+          referrerPda: null,
+        })
+        .signers([payer])
+        .rpc();
+
+    it("Should require a valid attestation for deposits above the threshold", async () => {
+      const token = Math.pow(10, 9);
+      await setKycConfig(attester.publicKey, 5 * token, 1);
+
+      // Small deposits are unaffected
+      await deposit(token, null);
+
+      try {
+        await deposit(10 * token, null);
+        expect.fail("Large deposit without an attestation should fail");
+      } catch (error) {
+        expect(error.message).to.include("KycRequired");
+      }
+
+      await program.methods
+          .attestUser(TEST_USER_ID, 1, new anchor.BN(Math.floor(Date.now() / 1000) + 3600))
+          .accounts({
+            programConfig: programConfigAddress,
+            userPda: userPdaAddress,
+            attestation: attestationAddress,
+            attester: attester.publicKey,
+            payer: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([attester, payer])
+          .rpc();
+
+      await deposit(10 * token, attestationAddress);
+
+      await program.methods
+          .revokeAttestation(TEST_USER_ID)
+          .accounts({
+            adminState: adminStateAddress,
+            userPda: userPdaAddress,
+            attestation: attestationAddress,
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+
+      try {
+        await deposit(10 * token, attestationAddress);
+        expect.fail("Revoked attestation should not count");
+      } catch (error) {
+        expect(error.message).to.include("KycRequired");
+      }
+
+      await setKycConfig(PublicKey.default, 0, 0);
+    });
+  });
+
+  describe("Account Migrations", () => {
+    let adminStateAddress: PublicKey;
+    let treasuryStateAddress: PublicKey;
+
+    before(async () => {
+      [adminStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("admin")],
+          program.programId
+      );
+      [treasuryStateAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("treasury"), testMint.toBuffer()],
+          program.programId
+      );
+    });
+
+    it("Creates accounts at the current version", async () => {
+      const adminState = await program.account.adminState.fetch(adminStateAddress);
+      const treasuryState = await program.account.treasuryState.fetch(treasuryStateAddress);
+      const userPda = await program.account.userPda.fetch(userPdaAddress);
+      expect(adminState.version).to.equal(1);
+      expect(treasuryState.version).to.equal(1);
+      expect(userPda.version).to.equal(1);
+    });
+
+    it("Leaves current accounts unchanged when migrating", async () => {
+      const before = await provider.connection.getAccountInfo(userPdaAddress);
+      const treasuryBefore = await provider.connection.getAccountInfo(treasuryStateAddress);
+
+      await program.methods
+          .migrateAdminState()
+          .accounts({
+            adminState: adminStateAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      await program.methods
+          .migrateTreasuryState(testMint)
+          .accounts({
+            adminState: adminStateAddress,
+            treasuryState: treasuryStateAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      await program.methods
+          .migrateUserPda(TEST_USER_ID)
+          .accounts({
+            adminState: adminStateAddress,
+            userPda: userPdaAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+
+      await program.methods
+          .migrateUserPdas()
+          .accounts({
+            adminState: adminStateAddress,
+            admin: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: userPdaAddress, isWritable: true, isSigner: false },
+          ])
+          .signers([payer])
+          .rpc();
+
+      const after = await provider.connection.getAccountInfo(userPdaAddress);
+      expect(after.data.length).to.equal(before.data.length);
+      expect(after.lamports).to.equal(before.lamports);
+      expect((await program.account.userPda.fetch(userPdaAddress)).version).to.equal(1);
+
+      const treasuryAfter = await provider.connection.getAccountInfo(treasuryStateAddress);
+      expect(treasuryAfter.data.length).to.equal(treasuryBefore.data.length);
+      expect(treasuryAfter.lamports).to.equal(treasuryBefore.lamports);
+    });
+
+    it("Rejects accounts that are not user PDAs", async () => {
+      try {
+        await program.methods
+            .migrateUserPdas()
+            .accounts({
+              adminState: adminStateAddress,
+              admin: payer.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts([
+              { pubkey: adminStateAddress, isWritable: true, isSigner: false },
+            ])
+            .signers([payer])
+            .rpc();
+        expect.fail("Admin state should not migrate as a user PDA");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMigrationAccount");
+      }
     });
   });
 
@@ -751,6 +3552,7 @@ describe("deposit-token-to-user-ata", () => {
   });
 
   describe("Batch Admin Transfer to Treasury", () => {
+    let programConfigAddress: PublicKey;
     let adminStateAddress: PublicKey;
     let treasuryStateAddress: PublicKey;
     let treasuryAtaAddress: PublicKey;
@@ -771,6 +3573,11 @@ describe("deposit-token-to-user-ata", () => {
     }[] = [];
 
     before(async () => {
+      [programConfigAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("config")],
+          program.programId
+      );
+
       // Calculate common addresses
       [adminStateAddress] = PublicKey.findProgramAddressSync(
           [Buffer.from("admin")],
@@ -799,11 +3606,12 @@ describe("deposit-token-to-user-ata", () => {
 
         // Create User PDA and ATA
         await program.methods
-            .createUserDepositAccount(user.userId)
+            .createUserDepositAccount(user.userId, null)
             .accounts({
               userPda: pda,
               userAta: ata,
               subscriptionTokenMint: testMint,
+              referrerPda: null,
               payer: payer.publicKey,
               tokenProgram: TOKEN_PROGRAM_ID,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -861,9 +3669,11 @@ describe("deposit-token-to-user-ata", () => {
           .accounts({
             adminState: adminStateAddress,
             treasuryState: treasuryStateAddress,
+            programConfig: programConfigAddress,
             treasuryAta: treasuryAtaAddress,
             mint: testMint,
             admin: payer.publicKey,
+            spendingLimit: spendingLimitAddress(payer.publicKey),
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })